@@ -0,0 +1,53 @@
+//! Links every solution in `src/bin` into the main binary, see `template::registry`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // solutions are named `<year>-<day>.rs`, e.g. `2024-01.rs`.
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let (year, day) = stem.split_once('-')?;
+                    let is_day = path.extension().is_some_and(|ext| ext == "rs")
+                        && year.len() == 4
+                        && day.len() == 2
+                        && stem.chars().all(|c| c.is_ascii_digit() || c == '-');
+                    is_day.then(|| (stem, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut out = String::new();
+
+    for (name, path) in &days {
+        // tests of the solutions already run as part of their own binaries.
+        out.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {path:?}]\nmod day_{};\n",
+            name.replace('-', "_")
+        ));
+    }
+
+    out.push_str("\n#[cfg(not(test))]\npub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (name, _) in &days {
+        // `YEAR` is derived from the binary name, which is `advent_of_code` when linked into the main binary.
+        out.push_str(&format!(
+            "    advent_of_code::template::registry::Solution {{\n        year: advent_of_code::template::Year::__from_bin_name({name:?}),\n        ..day_{}::SOLUTION\n    }},\n",
+            name.replace('-', "_")
+        ));
+    }
+    out.push_str("];\n");
+    out.push_str("\n#[cfg(test)]\npub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
@@ -104,7 +104,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(1227775554));
     }
 
@@ -116,7 +116,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(4174379265));
     }
 
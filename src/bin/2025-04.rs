@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use advent_of_code::{Coord, Grid};
+
+advent_of_code::solution!(4);
+
+const ROLL: char = '@';
+
+fn num_tp_neighbors(g: &Grid<char>, pos: Coord) -> u64 {
+    g.all_neighbors(pos).filter(|n| g[*n] == ROLL).count() as u64
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let g: Grid<char> = input.parse().unwrap();
+    Some(
+        g.positions_of(&ROLL)
+            .filter(|pos| num_tp_neighbors(&g, *pos) < 4)
+            .count() as u64,
+    )
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let g: Grid<char> = input.parse().unwrap();
+    let mut count_map = HashMap::new();
+    let mut stack = Vec::new();
+    let mut ans = 0;
+
+    for pos in g.positions_of(&ROLL) {
+        let neighbors = num_tp_neighbors(&g, pos);
+        if neighbors < 4 {
+            stack.push(pos);
+        }
+        count_map.insert(pos, neighbors);
+    }
+
+    while let Some(pos) = stack.pop() {
+        ans += 1;
+        for neighbor in g.all_neighbors(pos) {
+            count_map.entry(neighbor).and_modify(|e| {
+                *e -= 1;
+                if *e == 3 {
+                    stack.push(neighbor);
+                }
+            });
+        }
+    }
+    Some(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
+        assert_eq!(result, Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
+        assert_eq!(result, Some(43));
+    }
+}
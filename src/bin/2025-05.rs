@@ -53,7 +53,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let input: Input = input.parse().unwrap();
 
     let mut ranges = input.ranges_raw;
-    ranges.sort_by(|(l1, _), (l2, _)| l1.cmp(l2));
+    ranges.sort_by_key(|(l1, _)| *l1);
 
     let mut it = ranges.iter().peekable();
     let mut ans = 0;
@@ -81,13 +81,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(14));
     }
 }
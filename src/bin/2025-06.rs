@@ -56,7 +56,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         input
             .operations
             .into_iter()
-            .zip(op_nums.into_iter())
+            .zip(op_nums)
             .map(|(op, nums)| {
                 nums.into_iter()
                     .reduce(|l, r| match op {
@@ -111,13 +111,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(4277556));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(3263827));
     }
 }
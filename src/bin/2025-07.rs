@@ -0,0 +1,72 @@
+use std::collections::{HashMap, HashSet};
+
+use advent_of_code::{Coord, Grid};
+
+advent_of_code::solution!(7);
+
+const SPLITTER: char = '^';
+
+fn parse(input: &str) -> (Grid<char>, Coord) {
+    let g: Grid<char> = input.parse().unwrap();
+    let start = g.find(|&c| c == 'S').unwrap();
+    (g, start)
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let (g, (start_col, start_row)) = parse(input);
+    let mut beams: HashSet<usize> = [start_col].into_iter().collect();
+    let mut ans = 0u64;
+    for row in g.rows().skip(start_row + 1) {
+        let (new_beams, old_beams): (HashSet<usize>, HashSet<usize>) =
+            beams.iter().partition(|&&col| row.get(col) == Some(&SPLITTER));
+        ans += new_beams.len() as u64;
+        beams = old_beams.union(&new_beams
+            .iter()
+            .flat_map(|col| vec![col - 1, col + 1])
+            .collect()
+        ).cloned().collect()
+    }
+
+    Some(ans)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (g, (start_col, start_row)) = parse(input);
+    let mut beams: HashMap<usize, usize> = [(start_col, 1)].into_iter().collect();
+    for row in g.rows().skip(start_row + 1) {
+        let (split_beams, mut unsplit_beams): (HashMap<usize, usize>, HashMap<usize, usize>) =
+            beams
+                .iter()
+                .partition(|&(&col, _)| row.get(col) == Some(&SPLITTER));
+        for (new_beam, c) in split_beams {
+            unsplit_beams
+                .entry(new_beam - 1)
+                .and_modify(|v| *v += c)
+                .or_insert(c);
+            unsplit_beams
+                .entry(new_beam + 1)
+                .and_modify(|v| *v += c)
+                .or_insert(c);
+        }
+        beams = unsplit_beams;
+    }
+
+    Some(beams.values().sum::<usize>() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
+        assert_eq!(result, Some(21));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
+        assert_eq!(result, Some(40));
+    }
+}
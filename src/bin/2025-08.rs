@@ -0,0 +1,61 @@
+use advent_of_code::{DisjointSet, Point3};
+
+advent_of_code::solution!(8);
+
+/// Index pairs of all points, closest first.
+fn pairs_by_distance(coords: &[Point3]) -> Vec<(usize, usize)> {
+    let mut pairs: Vec<(u64, usize, usize)> = (0..coords.len())
+        .flat_map(|i| (i + 1..coords.len()).map(move |j| (i, j)))
+        .map(|(i, j)| (coords[i].distance_squared(coords[j]), i, j))
+        .collect();
+    pairs.sort_unstable();
+    pairs.into_iter().map(|(_, i, j)| (i, j)).collect()
+}
+
+fn parse(input: &str) -> Vec<Point3> {
+    input.lines().map(|line| line.parse().unwrap()).collect()
+}
+
+/// Connects the `pairs` closest pairs of points and multiplies the sizes of the three largest circuits.
+fn connect(coords: &[Point3], pairs: usize) -> u64 {
+    let mut circuits = DisjointSet::new(coords.len());
+    for (i, j) in pairs_by_distance(coords).into_iter().take(pairs) {
+        circuits.union(i, j);
+    }
+
+    circuits.component_sizes().take(3).product::<usize>() as u64
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(connect(&parse(input), 1000))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let coords = parse(input);
+    let mut circuits = DisjointSet::new(coords.len());
+    for (i, j) in pairs_by_distance(&coords) {
+        if circuits.union(i, j) && circuits.component_count() == 1 {
+            return Some((coords[i].x * coords[j].x) as u64);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        // the example connects its 10 closest pairs instead of 1000.
+        let coords = parse(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
+        assert_eq!(connect(&coords, 10), 40);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
+        assert_eq!(result, Some(25272));
+    }
+}
@@ -1,5 +1,5 @@
 use core::fmt;
-use std::{fmt::format, str::FromStr};
+use std::str::FromStr;
 
 use itertools::Itertools;
 
@@ -9,7 +9,6 @@ advent_of_code::solution!(10);
 struct Machine {
     lights: u32,
     switches: Vec<u32>,
-    joltage: (),
 }
 
 fn parse_switch(s: &str) -> u32 {
@@ -41,13 +40,9 @@ impl FromStr for Machine {
 
         let switches: Vec<u32> = parts
             .take_while(|s| s.starts_with("("))
-            .map(|switch_str| parse_switch(switch_str))
+            .map(parse_switch)
             .collect();
-        Ok(Self {
-            lights,
-            switches,
-            joltage: (),
-        })
+        Ok(Self { lights, switches })
     }
 }
 
@@ -104,7 +99,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(_input: &str) -> Option<u64> {
     None
 }
 
@@ -114,13 +109,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, Some(7));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_year_file(YEAR, "examples", DAY));
         assert_eq!(result, None);
     }
 
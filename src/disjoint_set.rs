@@ -0,0 +1,232 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Union-find over the elements `0..len()`, with path compression and union by size.
+#[derive(Clone, Debug, Default)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Creates `len` elements, each in its own component.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Adds an element in its own component and returns it.
+    pub fn push(&mut self) -> usize {
+        let element = self.len();
+        self.parents.push(element);
+        self.sizes.push(1);
+        self.components += 1;
+        element
+    }
+
+    /// Representative of the component containing `element`.
+    pub fn find(&mut self, mut element: usize) -> usize {
+        // path halving, every visited element is pointed to its grandparent.
+        while self.parents[element] != element {
+            let parent = self.parents[element];
+            self.parents[element] = self.parents[parent];
+            element = parent;
+        }
+        element
+    }
+
+    /// Merges the components of `a` and `b`. Returns `false` if they already were the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Number of elements in the component containing `element`.
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Sizes of all components, largest first.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + use<> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect();
+
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.into_iter()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Union-find over arbitrary values. Values are added on first use.
+#[derive(Clone, Debug)]
+pub struct KeyedDisjointSet<T> {
+    indices: HashMap<T, usize>,
+    keys: Vec<T>,
+    set: DisjointSet,
+}
+
+impl<T> Default for KeyedDisjointSet<T> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            keys: Vec::new(),
+            set: DisjointSet::default(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> KeyedDisjointSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Adds `key` in its own component, unless it is known already. Returns its index.
+    pub fn insert(&mut self, key: T) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+
+        let index = self.set.push();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        index
+    }
+
+    /// Representative of the component containing `key`, or `None` if `key` is unknown.
+    pub fn find(&mut self, key: &T) -> Option<T> {
+        let index = *self.indices.get(key)?;
+        let root = self.set.find(index);
+        Some(self.keys[root].clone())
+    }
+
+    /// Merges the components of `a` and `b`, adding them if they are unknown. Returns `false` if they already were the same component.
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        self.set.union(a, b)
+    }
+
+    /// Whether `a` and `b` are known and in the same component.
+    pub fn connected(&mut self, a: &T, b: &T) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.set.connected(a, b),
+            _ => false,
+        }
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.set.component_count()
+    }
+
+    /// Number of values in the component containing `key`, 0 if `key` is unknown.
+    pub fn size_of(&mut self, key: &T) -> usize {
+        match self.indices.get(key) {
+            Some(&index) => self.set.size_of(index),
+            None => 0,
+        }
+    }
+
+    /// Sizes of all components, largest first.
+    pub fn component_sizes(&self) -> impl Iterator<Item = usize> + use<T> {
+        self.set.component_sizes()
+    }
+}
+
+impl<T: Hash + Eq + Clone> FromIterator<T> for KeyedDisjointSet<T> {
+    /// Adds every value in its own component.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for key in iter {
+            set.insert(key);
+        }
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DisjointSet, KeyedDisjointSet};
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(set.union(3, 4));
+        assert!(!set.union(0, 2));
+
+        assert!(set.connected(0, 2));
+        assert!(!set.connected(0, 3));
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.size_of(2), 3);
+        assert_eq!(set.component_sizes().collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(set.component_sizes().take(2).product::<usize>(), 6);
+
+        let added = set.push();
+        assert_eq!((added, set.component_count()), (6, 4));
+    }
+
+    #[test]
+    fn merges_keyed_components() {
+        let mut set: KeyedDisjointSet<&str> = ["a", "b", "c"].into_iter().collect();
+
+        assert!(set.union("a", "b"));
+        assert!(set.union("d", "e"));
+        assert!(!set.union("b", "a"));
+
+        assert_eq!(set.len(), 5);
+        assert_eq!(set.find(&"a"), set.find(&"b"));
+        assert_eq!(set.find(&"x"), None);
+        assert!(set.connected(&"d", &"e"));
+        assert!(!set.connected(&"a", &"x"));
+        assert_eq!(set.size_of(&"e"), 2);
+        assert_eq!(set.size_of(&"x"), 0);
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.component_sizes().collect::<Vec<_>>(), [2, 2, 1]);
+    }
+}
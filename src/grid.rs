@@ -0,0 +1,250 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{Coord, all_neighbors, ortho_neighbors};
+
+/// Position a [`Grid`] can be indexed with.
+pub trait Position: Copy {
+    /// The position as `(x, y)`, or `None` if it can not lie inside any grid, e.g. because it is negative.
+    fn to_coord(self) -> Option<Coord>;
+}
+
+impl Position for Coord {
+    fn to_coord(self) -> Option<Coord> {
+        Some(self)
+    }
+}
+
+/// Dense two-dimensional grid, stored row by row. Positions are `(x, y)`, i.e. `(column, row)`, starting top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` by `height` cells set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses one row per line, mapping every character with `f`. Fails if the rows differ in length.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> T) -> Result<Self, String> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in s.lines().enumerate() {
+            let len = line.chars().count();
+
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(format!(
+                        "Expected row {y} to have {width} cells, found {len}."
+                    ));
+                }
+                Some(_) => {}
+            }
+
+            cells.extend(line.chars().map(&mut f));
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` lies inside the grid.
+    pub fn contains(&self, pos: impl Position) -> bool {
+        self.offset(pos).is_some()
+    }
+
+    pub fn get(&self, pos: impl Position) -> Option<&T> {
+        self.offset(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Position) -> Option<&mut T> {
+        self.offset(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Coord> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks` does not accept a size of 0, an empty grid has no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Position of the first cell matching `predicate`, row by row.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter()
+            .find_map(|(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    /// Positions of all cells equal to `value`, row by row.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Coord> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(pos, cell)| (cell == value).then_some(pos))
+    }
+
+    /// Horizontal and vertical neighbors of `pos` inside the grid.
+    pub fn ortho_neighbors(&self, pos: Coord) -> impl Iterator<Item = Coord> + use<T> {
+        ortho_neighbors(pos, self.width, self.height)
+    }
+
+    /// Horizontal, vertical and diagonal neighbors of `pos` inside the grid.
+    pub fn all_neighbors(&self, pos: Coord) -> impl Iterator<Item = Coord> + use<T> {
+        all_neighbors(pos, self.width, self.height)
+    }
+
+    /// Creates a grid of the same size with every cell mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn offset(&self, pos: impl Position) -> Option<usize> {
+        let (x, y) = pos.to_coord()?;
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+}
+
+impl<T, P: Position + fmt::Debug> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl<T, P: Position + fmt::Debug> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is out of bounds"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |c| c)
+    }
+}
+
+/// Prints one line per row, without separators between the cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::Point2;
+
+    const INPUT: &str = "#..\n.#S\n";
+
+    #[test]
+    fn parses_and_indexes() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'S');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid[Point2::new(1, 1)], '#');
+        assert_eq!(grid.get(Point2::new(-1, 0)), None);
+        assert_eq!(grid.row(1), ['.', '#', 'S']);
+        assert_eq!(grid.column(1).collect::<String>(), ".#");
+        assert_eq!(grid.to_string(), INPUT);
+
+        assert!(Grid::parse("##\n#\n", |c| c).is_err());
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = Grid::parse(INPUT, |c| c == '#').unwrap();
+
+        assert_eq!(grid.find(|&wall| !wall), Some((1, 0)));
+        assert_eq!(
+            grid.positions_of(&true).collect::<Vec<_>>(),
+            [(0, 0), (1, 1)]
+        );
+
+        let walls = grid.map(|&wall| u8::from(wall));
+        assert_eq!(walls.to_string(), "100\n010\n");
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let mut grid = Grid::new(3, 3, 0);
+        for pos in grid.all_neighbors((0, 0)).collect::<Vec<_>>() {
+            grid[pos] += 1;
+        }
+        for pos in grid.ortho_neighbors((1, 1)).collect::<Vec<_>>() {
+            grid[pos] += 1;
+        }
+
+        assert_eq!(grid.to_string(), "020\n211\n010\n");
+    }
+}
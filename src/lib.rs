@@ -1,42 +1,33 @@
 pub mod template;
 
-pub type Coord = (usize, usize);
+mod disjoint_set;
+mod grid;
+mod point;
+pub mod search;
+pub use disjoint_set::{DisjointSet, KeyedDisjointSet};
+pub use grid::{Grid, Position};
+pub use point::{Dir4, Dir8, Point2, Point3};
 
-pub fn ortho_neighbors(
-    (x, y): (usize, usize),
-    w: usize,
-    h: usize,
-) -> impl Iterator<Item = (usize, usize)> {
-    const DIRS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+pub type Coord = (usize, usize);
 
-    DIRS.into_iter().filter_map(move |(dx, dy)| {
-        let nx = x.checked_add_signed(dx)?;
-        let ny = y.checked_add_signed(dy)?;
-        (nx < w && ny < h).then_some((nx, ny))
-    })
+/// Horizontal and vertical neighbors inside a `w` by `h` area, clockwise starting with the one above.
+pub fn ortho_neighbors(pos: Coord, w: usize, h: usize) -> impl Iterator<Item = Coord> {
+    Dir4::ALL
+        .into_iter()
+        .filter_map(move |dir| step_within(pos, dir.delta(), w, h))
 }
 
-pub fn all_neighbors(
-    (x, y): (usize, usize),
-    w: usize,
-    h: usize,
-) -> impl Iterator<Item = (usize, usize)> {
-    const DIRS: [(isize, isize); 8] = [
-        (-1, 1),
-        (0, -1),
-        (1, -1),
-        (-1, 0),
-        (1, 0),
-        (-1, -1),
-        (0, 1),
-        (1, 1),
-    ];
+/// All eight neighbors inside a `w` by `h` area, clockwise starting with the one above.
+pub fn all_neighbors(pos: Coord, w: usize, h: usize) -> impl Iterator<Item = Coord> {
+    Dir8::ALL
+        .into_iter()
+        .filter_map(move |dir| step_within(pos, dir.delta(), w, h))
+}
 
-    DIRS.into_iter().filter_map(move |(dx, dy)| {
-        let nx = x.checked_add_signed(dx)?;
-        let ny = y.checked_add_signed(dy)?;
-        (nx < w && ny < h).then_some((nx, ny))
-    })
+fn step_within((x, y): Coord, delta: Point2, w: usize, h: usize) -> Option<Coord> {
+    let nx = x.checked_add_signed(delta.x as isize)?;
+    let ny = y.checked_add_signed(delta.y as isize)?;
+    (nx < w && ny < h).then_some((nx, ny))
 }
 
 pub trait Transpose
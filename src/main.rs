@@ -1,14 +1,24 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, dhat, download, read, scaffold, solve, time, verify, watch,
+};
+use advent_of_code::template::Execution;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        Day,
+        commands::{
+            dhat::{self, SortBy},
+            read::Section,
+            time,
+            watch::Action,
+        },
+        runner::BenchConfig,
+    };
+    use std::{path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -16,11 +26,14 @@ mod args {
         },
         Read {
             day: Day,
+            section: Section,
+            refresh: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
@@ -30,14 +43,42 @@ mod args {
         },
         All {
             release: bool,
+            in_process: bool,
+            jobs: usize,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            bench: BenchConfig,
+            in_process: bool,
+            jobs: usize,
+        },
+        TimeHistory {
+            day: Day,
+        },
+        Verify {
+            day: Option<Day>,
+            accept: bool,
+            release: bool,
+            in_process: bool,
+            jobs: usize,
+        },
+        Dhat {
+            file: Option<PathBuf>,
+            diff: Option<PathBuf>,
+            sort: SortBy,
+            top: usize,
+        },
+        WatchDay {
+            day: Day,
+            action: Action,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -46,27 +87,78 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                in_process: args.contains("--in-process"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+            },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                day: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let in_process = args.contains("--in-process");
+                // NOTE: benching stays sequential unless parallel runs are explicitly requested.
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(time::DEFAULT_THRESHOLD);
+
+                let defaults = BenchConfig::default();
+                let bench = BenchConfig {
+                    warm_up: args
+                        .opt_value_from_str("--warm-up")?
+                        .map_or(defaults.warm_up, Duration::from_millis),
+                    measurement_time: args
+                        .opt_value_from_str("--measurement-time")?
+                        .map_or(defaults.measurement_time, Duration::from_millis),
+                    min_samples: args
+                        .opt_value_from_str("--min-samples")?
+                        .unwrap_or(defaults.min_samples),
+                    max_samples: args
+                        .opt_value_from_str("--max-samples")?
+                        .unwrap_or(defaults.max_samples),
+                };
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare: compare.then_some(threshold),
+                    bench,
+                    in_process,
+                    jobs,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            Some("verify") => AppArguments::Verify {
+                accept: args.contains("--accept"),
+                release: args.contains("--release"),
+                in_process: args.contains("--in-process"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                day: args.opt_free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("read") => {
+                let section = match args.opt_value_from_str::<_, u8>("--part")? {
+                    _ if args.contains("--examples") => Section::Examples,
+                    Some(part @ (1 | 2)) => Section::Part(part),
+                    Some(_) => return Err("--part must be 1 or 2".into()),
+                    None => Section::All,
+                };
+
+                AppArguments::Read {
+                    day: args.free_from_str()?,
+                    section,
+                    refresh: args.contains("--refresh"),
+                }
+            }
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
@@ -74,8 +166,39 @@ mod args {
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
             },
+            Some("dhat") => AppArguments::Dhat {
+                diff: args.opt_value_from_str("--diff")?,
+                sort: args.opt_value_from_str("--sort")?.unwrap_or(SortBy::Bytes),
+                top: args.opt_value_from_str("--top")?.unwrap_or(dhat::DEFAULT_TOP),
+                file: args.opt_free_from_str()?,
+            },
+            Some("watch-day") => {
+                let actions: Vec<Action> = [
+                    ("--test", Action::Test),
+                    ("--solve", Action::Solve),
+                    ("--example", Action::Example),
+                ]
+                .into_iter()
+                .filter_map(|(flag, action)| args.contains(flag).then_some(action))
+                .collect();
+
+                let action = match actions[..] {
+                    [] => Action::Test,
+                    [action] => action,
+                    _ => {
+                        return Err("only one of --test, --solve and --example can be passed".into());
+                    }
+                };
+
+                AppArguments::WatchDay {
+                    day: args.free_from_str()?,
+                    action,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -95,6 +218,19 @@ mod args {
     }
 }
 
+/// Solutions of all scaffolded days, linked into this binary by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+fn execution(in_process: bool, is_release: bool) -> Execution<'static> {
+    if in_process {
+        Execution::InProcess(solutions::SOLUTIONS)
+    } else {
+        Execution::ChildProcess { is_release }
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -102,19 +238,53 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                in_process,
+                jobs,
+            } => all::handle(execution(in_process, release), jobs),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                bench,
+                in_process,
+                jobs,
+            } => time::handle(
+                day,
+                all,
+                store,
+                compare,
+                &bench,
+                execution(in_process, true),
+                jobs,
+            ),
+            AppArguments::TimeHistory { day } => time::handle_history(day),
+            AppArguments::Verify {
+                day,
+                accept,
+                release,
+                in_process,
+                jobs,
+            } => verify::handle(day, accept, execution(in_process, release), jobs),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Read {
+                day,
+                section,
+                refresh,
+            } => read::handle(day, section, refresh),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // download first, so the template can use the title of the puzzle.
                 if download {
                     download::handle(day);
                 }
+                scaffold::handle(day, overwrite, template.as_deref());
             }
             AppArguments::Solve {
                 day,
@@ -122,23 +292,15 @@ fn main() {
                 dhat,
                 submit,
             } => solve::handle(day, release, dhat, submit),
+            AppArguments::Dhat {
+                file,
+                diff,
+                sort,
+                top,
+            } => dhat::handle(file.as_deref(), diff.as_deref(), sort, top),
+            AppArguments::WatchDay { day, action } => watch::handle(day, action),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 12th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
@@ -0,0 +1,453 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{Coord, Position};
+
+/// Signed point in 2D. Like [`Coord`], `y` grows downwards, so `Dir4::Up` is `(0, -1)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Taxicab distance, i.e. the number of horizontal and vertical steps between the points.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Chessboard distance, i.e. the number of steps between the points if diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The four horizontal and vertical neighbors, clockwise starting with the one above.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir.delta())
+    }
+
+    /// All eight neighbors, clockwise starting with the one above.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir.delta())
+    }
+}
+
+impl fmt::Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl TryFrom<Coord> for Point2 {
+    type Error = String;
+
+    fn try_from((x, y): Coord) -> Result<Self, Self::Error> {
+        match (i64::try_from(x), i64::try_from(y)) {
+            (Ok(x), Ok(y)) => Ok(Self::new(x, y)),
+            _ => Err(format!("Coord ({x}, {y}) does not fit into a point.")),
+        }
+    }
+}
+
+impl TryFrom<Point2> for Coord {
+    type Error = String;
+
+    fn try_from(value: Point2) -> Result<Self, Self::Error> {
+        match (usize::try_from(value.x), usize::try_from(value.y)) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(format!("Point {value} is not a valid coord.")),
+        }
+    }
+}
+
+impl Position for Point2 {
+    fn to_coord(self) -> Option<Coord> {
+        Coord::try_from(self).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Signed point in 3D.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Square of the straight-line distance. Orders points like the distance itself, without rounding errors.
+    pub fn distance_squared(self, other: Self) -> u64 {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        let dz = self.z.abs_diff(other.z);
+        dx * dx + dy * dy + dz * dz
+    }
+
+    /// Taxicab distance, i.e. the number of steps along the axes between the points.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The six neighbors sharing a face with this point.
+    pub fn neighbors6(self) -> impl Iterator<Item = Self> {
+        const DELTAS: [Point3; 6] = [
+            Point3::new(1, 0, 0),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(0, 0, -1),
+        ];
+
+        DELTAS.into_iter().map(move |delta| self + delta)
+    }
+
+    /// The 26 neighbors sharing a face, an edge or a corner with this point.
+    pub fn neighbors26(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Self::new(x, y, z))))
+            .filter(|&delta| delta != Self::ORIGIN)
+            .map(move |delta| self + delta)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Parses `x,y,z`, surrounding whitespace is ignored.
+impl FromStr for Point3 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().split(',').map(str::trim).collect();
+
+        let &[x, y, z] = parts.as_slice() else {
+            return Err(format!("Expected 3 coordinates in \"{s}\"."));
+        };
+
+        let number = |part: &str| {
+            part.parse::<i64>()
+                .map_err(|e| format!("Invalid coordinate \"{part}\" in \"{s}\": {e}"))
+        };
+
+        Ok(Self::new(number(x)?, number(y)?, number(z)?))
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Horizontal or vertical direction, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Turns 90° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Offset of a single step in this direction.
+    pub fn delta(self) -> Point2 {
+        match self {
+            Self::Up => Point2::new(0, -1),
+            Self::Right => Point2::new(1, 0),
+            Self::Down => Point2::new(0, 1),
+            Self::Left => Point2::new(-1, 0),
+        }
+    }
+}
+
+/// Accepts arrows (`^>v<`), compass points (`NESW`) and initials (`UDLR`).
+impl TryFrom<char> for Dir4 {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' | 'U' => Ok(Self::Up),
+            '>' | 'E' | 'R' => Ok(Self::Right),
+            'v' | 'S' | 'D' => Ok(Self::Down),
+            '<' | 'W' | 'L' => Ok(Self::Left),
+            _ => Err(format!("Invalid direction: {value}")),
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(format!("Invalid direction: {s}")),
+        }
+    }
+}
+
+/// Horizontal, vertical or diagonal direction, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turns 45° counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Turns 45° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Offset of a single step in this direction.
+    pub fn delta(self) -> Point2 {
+        match self {
+            Self::Up => Point2::new(0, -1),
+            Self::UpRight => Point2::new(1, -1),
+            Self::Right => Point2::new(1, 0),
+            Self::DownRight => Point2::new(1, 1),
+            Self::Down => Point2::new(0, 1),
+            Self::DownLeft => Point2::new(-1, 1),
+            Self::Left => Point2::new(-1, 0),
+            Self::UpLeft => Point2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(value: Dir4) -> Self {
+        Self::ALL[value as usize * 2]
+    }
+}
+
+/// Accepts everything [`Dir4`] does and the compass points `NE`, `SE`, `SW` and `NW`.
+impl FromStr for Dir8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Self::UpRight),
+            "SE" => Ok(Self::DownRight),
+            "SW" => Ok(Self::DownLeft),
+            "NW" => Ok(Self::UpLeft),
+            _ => s.parse::<Dir4>().map(Self::from),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Point2, Point3};
+    use crate::Coord;
+
+    #[test]
+    fn computes_with_points() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 4);
+
+        assert_eq!(a + b, Point2::new(-2, 2));
+        assert_eq!(a - b, Point2::new(4, -6));
+        assert_eq!(a * 3, Point2::new(3, -6));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point2::ORIGIN.neighbors8().count(), 8);
+
+        assert_eq!(Coord::try_from(Point2::new(2, 3)), Ok((2, 3)));
+        assert!(Coord::try_from(a).is_err());
+        assert_eq!(Point2::try_from((2, 3)), Ok(Point2::new(2, 3)));
+    }
+
+    #[test]
+    fn turns_and_parses_directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+
+        for dir in Dir4::ALL {
+            assert_eq!(dir.delta(), Dir8::from(dir).delta());
+            assert_eq!(dir.opposite().delta(), -dir.delta());
+        }
+
+        let parsed: Vec<Dir4> = "^>v<".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(parsed, Dir4::ALL);
+        assert_eq!("W".parse::<Dir4>(), Ok(Dir4::Left));
+        assert_eq!("D".parse::<Dir4>(), Ok(Dir4::Down));
+        assert!("UD".parse::<Dir4>().is_err());
+        assert_eq!("SW".parse::<Dir8>(), Ok(Dir8::DownLeft));
+        assert_eq!("N".parse::<Dir8>(), Ok(Dir8::Up));
+    }
+
+    #[test]
+    fn computes_with_3d_points() {
+        let a: Point3 = "1,-2,3".parse().unwrap();
+        let b: Point3 = " 4, 2, 3\n".parse().unwrap();
+
+        assert_eq!(a, Point3::new(1, -2, 3));
+        assert_eq!(b - a, Point3::new(3, 4, 0));
+        assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.neighbors6().count(), 6);
+        assert!(
+            a.neighbors26()
+                .all(|n| n != a && (n - a).manhattan(Point3::ORIGIN) <= 3)
+        );
+        assert_eq!(a.neighbors26().count(), 26);
+
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        assert!("1,x,3".parse::<Point3>().is_err());
+    }
+}
@@ -0,0 +1,520 @@
+//! Shortest paths over implicit graphs, i.e. graphs given by a function from a state to its successors.
+//! States can be anything hashable, e.g. `Coord`s of a `Grid` or a `(Point2, Dir4)` pair.
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Cheapest path found by a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPath<S, C> {
+    /// Total cost of the path, the number of steps for [`bfs`].
+    pub cost: C,
+    /// States along the path, from the start to the goal.
+    pub states: Vec<S>,
+}
+
+/// Breadth-first search from `starts` to the first state matching `goal`.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, Option<S>> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if goal(&state) {
+            return Some(ShortestPath {
+                cost: steps,
+                states: reconstruct(&parents, state),
+            });
+        }
+
+        for next in successors(&state) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(state.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of steps from the closest of `starts` to every reachable state.
+pub fn bfs_distances<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Like [`dijkstra_all`], with every step costing 1.
+pub fn bfs_all<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<AllShortestPaths<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    dijkstra_all(
+        starts,
+        |state| successors(state).into_iter().map(|next| (next, 1)),
+        goal,
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Cheapest path from `starts` to the first state matching `goal`. Successors are `(state, cost)` pairs, costs must not be negative.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), goal)
+}
+
+/// Cost from the cheapest of `starts` to every reachable state.
+pub fn dijkstra_distances<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(starts, successors, |_| C::default(), |_| false).costs
+}
+
+/// Like [`dijkstra`], but explores states with a low `cost + heuristic(state)` first.
+/// The heuristic must never overestimate the remaining cost to a goal, e.g. the manhattan distance on a grid.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<ShortestPath<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let search = best_first(starts, successors, heuristic, goal);
+    let found = search.found?;
+
+    Some(ShortestPath {
+        cost: search.costs[&found],
+        states: reconstruct(&search.parents, found),
+    })
+}
+
+/// All cheapest paths from `starts` to the states matching `goal`. Steps may cost nothing, the paths never visit a state twice.
+pub fn dijkstra_all<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<AllShortestPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs: HashMap<S, C> = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    let mut goals = Vec::new();
+    let mut goal_cost = None;
+    let starts: HashSet<S> = starts.into_iter().collect();
+
+    for start in &starts {
+        costs.insert(start.clone(), C::default());
+        heap.push(Entry::new(C::default(), C::default(), start.clone()));
+    }
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if cost > costs[&state] {
+            continue;
+        }
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        if goal(&state) {
+            goal_cost = Some(cost);
+            goals.push(state);
+            continue;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+
+            match costs.get(&next).map(|best| next_cost.cmp(best)) {
+                Some(Ordering::Greater) => {}
+                // every path through a start begins there, even if a zero-cost step leads back to it.
+                Some(Ordering::Equal) if starts.contains(&next) => {}
+                Some(Ordering::Equal) => predecessors.entry(next).or_default().push(state.clone()),
+                Some(Ordering::Less) | None => {
+                    costs.insert(next.clone(), next_cost);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Entry::new(next_cost, next_cost, next));
+                }
+            }
+        }
+    }
+
+    Some(AllShortestPaths {
+        cost: goal_cost?,
+        goals,
+        predecessors,
+    })
+}
+
+/// Every cheapest path to the goals of a search, see [`dijkstra_all`].
+#[derive(Clone, Debug)]
+pub struct AllShortestPaths<S, C> {
+    /// Cost of each of the paths.
+    pub cost: C,
+    /// The goal states reached at that cost.
+    pub goals: Vec<S>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C> AllShortestPaths<S, C> {
+    /// States that lie on any of the paths, including starts and goals.
+    pub fn states(&self) -> HashSet<S> {
+        let mut seen: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Every path from a start to a goal. Their number can grow exponentially, prefer [`Self::states`] where possible.
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut paths = Vec::new();
+
+        for goal in &self.goals {
+            self.collect_paths(vec![goal.clone()], &mut paths);
+        }
+
+        paths
+    }
+
+    fn collect_paths(&self, path: Vec<S>, paths: &mut Vec<Vec<S>>) {
+        let last = path.last().unwrap();
+
+        match self.predecessors.get(last) {
+            Some(previous) => {
+                // zero-cost steps can make states each other's predecessors.
+                for state in previous.iter().filter(|&state| !path.contains(state)) {
+                    let mut longer = path.clone();
+                    longer.push(state.clone());
+                    self.collect_paths(longer, paths);
+                }
+            }
+            None => paths.push(path.into_iter().rev().collect()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Result of [`best_first`]. `found` is the goal that was reached, if any.
+struct Search<S, C> {
+    found: Option<S>,
+    costs: HashMap<S, C>,
+    parents: HashMap<S, Option<S>>,
+}
+
+fn best_first<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs: HashMap<S, C> = HashMap::new();
+    let mut parents: HashMap<S, Option<S>> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if costs.contains_key(&start) {
+            continue;
+        }
+        costs.insert(start.clone(), C::default());
+        parents.insert(start.clone(), None);
+        heap.push(Entry::new(heuristic(&start), C::default(), start));
+    }
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        // the state was reached more cheaply after this entry was queued.
+        if cost > costs[&state] {
+            continue;
+        }
+        if goal(&state) {
+            return Search {
+                found: Some(state),
+                costs,
+                parents,
+            };
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), Some(state.clone()));
+                heap.push(Entry::new(next_cost + heuristic(&next), next_cost, next));
+            }
+        }
+    }
+
+    Search {
+        found: None,
+        costs,
+        parents,
+    }
+}
+
+fn reconstruct<S: Clone + Eq + Hash>(parents: &HashMap<S, Option<S>>, goal: S) -> Vec<S> {
+    let mut states = vec![goal];
+
+    while let Some(Some(parent)) = parents.get(states.last().unwrap()) {
+        states.push(parent.clone());
+    }
+
+    states.reverse();
+    states
+}
+
+/// Queued state, ordered so that `BinaryHeap` pops the lowest `priority` first.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C> Entry<S, C> {
+    fn new(priority: C, cost: C, state: S) -> Self {
+        Self {
+            priority,
+            cost,
+            state,
+        }
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // prefers the higher cost on ties, i.e. entries closer to the goal.
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_all, bfs_distances, dijkstra, dijkstra_all, dijkstra_distances};
+    use crate::{Coord, Grid, Point2};
+
+    const MAZE: &str = "\
+S..#.
+.#...
+...E.
+";
+
+    fn open_neighbors(grid: &Grid<char>, pos: Coord) -> impl Iterator<Item = Coord> + '_ {
+        grid.ortho_neighbors(pos).filter(|&next| grid[next] != '#')
+    }
+
+    #[test]
+    fn searches_grids_breadth_first() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+
+        let path = bfs(
+            [start],
+            |&pos| open_neighbors(&grid, pos),
+            |&pos| pos == end,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.len(), 6);
+        assert_eq!((path.states[0], path.states[5]), (start, end));
+
+        let distances = bfs_distances([end], |&pos| open_neighbors(&grid, pos));
+        assert_eq!(distances[&start], 5);
+        assert_eq!(distances.len(), 13);
+
+        // both starts are searched at once, the one next to the end wins.
+        let path = bfs(
+            [start, (2, 2)],
+            |&pos| open_neighbors(&grid, pos),
+            |&pos| pos == end,
+        );
+        assert_eq!(path.unwrap().cost, 1);
+
+        let all = bfs_all(
+            [start],
+            |&pos| open_neighbors(&grid, pos),
+            |&pos| pos == end,
+        )
+        .unwrap();
+        assert_eq!(all.cost, 5);
+        assert_eq!(all.paths().len(), 3);
+        // the last column is not on any of the paths.
+        assert_eq!(all.states().len(), 10);
+
+        assert!(bfs([start], |&pos| open_neighbors(&grid, pos), |_| false).is_none());
+    }
+
+    #[test]
+    fn searches_weighted_graphs() {
+        // a -> b is cheap but b -> d is expensive.
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('c', 4)],
+            'b' => vec![('d', 10), ('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        };
+
+        let path = dijkstra(['a'], edges, |&node| node == 'd').unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states, ['a', 'b', 'c', 'd']);
+
+        let costs = dijkstra_distances(['a'], edges);
+        assert_eq!(costs[&'c'], 3);
+        assert_eq!(costs[&'d'], 6);
+
+        let all = dijkstra_all(['a'], edges, |&node| node == 'd').unwrap();
+        assert_eq!(all.cost, 6);
+        assert_eq!(all.paths(), [vec!['a', 'b', 'c', 'd']]);
+
+        let tied = dijkstra_all(
+            ['a'],
+            |node: &char| match node {
+                'a' => vec![('b', 1), ('c', 1)],
+                'b' | 'c' => vec![('d', 1)],
+                _ => vec![],
+            },
+            |&node| node == 'd',
+        )
+        .unwrap();
+        assert_eq!(tied.paths().len(), 2);
+        assert_eq!(tied.states().len(), 4);
+
+        // a repeated start does not repeat the paths.
+        let repeated = dijkstra_all(['a', 'a'], edges, |&node| node == 'd').unwrap();
+        assert_eq!(repeated.paths(), [vec!['a', 'b', 'c', 'd']]);
+    }
+
+    #[test]
+    fn finds_all_paths_with_zero_cost_cycles() {
+        // b and c are connected for free in both directions, as are the start a and e.
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('e', 0)],
+            'b' => vec![('c', 0), ('d', 1)],
+            'c' => vec![('b', 0), ('d', 1)],
+            'e' => vec![('a', 0)],
+            _ => vec![],
+        };
+
+        let all = dijkstra_all(['a'], edges, |&node| node == 'd').unwrap();
+        assert_eq!(all.cost, 2);
+        let mut paths = all.paths();
+        paths.sort();
+        assert_eq!(paths, [vec!['a', 'b', 'c', 'd'], vec!['a', 'b', 'd']]);
+        assert_eq!(all.states().len(), 4);
+    }
+
+    #[test]
+    fn searches_with_heuristic() {
+        let goal = Point2::new(5, -3);
+        let path = astar(
+            [Point2::ORIGIN],
+            |&pos| pos.neighbors4().map(|next| (next, 1_u64)),
+            |&pos| pos.manhattan(goal),
+            |&pos| pos == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 8);
+        assert_eq!(path.states.len(), 9);
+    }
+}
@@ -0,0 +1,218 @@
+/// Counts heap allocations of solution parts.
+/// The counting allocator is registered as global allocator with the `count-allocs` feature.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tinyjson::JsonValue;
+
+/// Heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocs: u64,
+    /// Total bytes allocated.
+    pub bytes: u64,
+    /// Maximum of bytes that were live at the same time, on top of the bytes live before the run.
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// One-line human-readable summary, e.g. `12 allocs · 3.4 KiB · peak 1.0 KiB`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} allocs · {} · peak {}",
+            self.allocs,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `512 B` or `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Whether the counting allocator is registered. DHAT takes precedence if both features are enabled.
+pub const ENABLED: bool = cfg!(all(feature = "count-allocs", not(feature = "dhat-heap")));
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator that forwards to the system allocator and counts allocations.
+/// The counters are process-wide, so parts running concurrently on other threads are counted, too.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn on_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // counted like freeing the old block and allocating a new one.
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `func` and counts its allocations. Returns `None` for the stats if the counting allocator is not registered.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !ENABLED {
+        return (func(), None);
+    }
+
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = AllocStats {
+        allocs: ALLOCS.load(Ordering::Relaxed) - allocs,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+
+    (result, Some(stats))
+}
+
+/// Reads optional allocation stats from `key` of a JSON object. A missing key or `null` is read as `None`.
+pub fn optional_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<AllocStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => AllocStats::try_from(v).map(Some),
+    }
+}
+
+/// Converts optional allocation stats into a JSON value, `None` becomes `null`.
+pub fn optional_to_json(stats: Option<&AllocStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("allocs".into(), JsonValue::Number(value.allocs as f64));
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert("peak".into(), JsonValue::Number(value.peak as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocs: number("allocs")?,
+            bytes: number("bytes")?,
+            peak: number("peak")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{AllocStats, format_bytes};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = AllocStats {
+            allocs: 12,
+            bytes: 3481,
+            peak: 1024,
+        };
+
+        let json = JsonValue::from(&stats);
+        assert_eq!(AllocStats::try_from(&json).unwrap(), stats);
+        assert_eq!(stats.summary(), "12 allocs · 3.4 KiB · peak 1.0 KiB");
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+/// Represents the accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(file_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(file_path())
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// The accepted answer of a part, if one was recorded.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the accepted answer of a part, overwriting a previous one.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.to_string()),
+            2 => answer.part_2 = Some(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Path of the answers file of the selected year, e.g. `data/2025/answers.json`.
+fn file_path() -> PathBuf {
+    Year::selected().data_dir().join("answers.json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "08", "part_1": "123", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(8), 1), Some("123"));
+        assert_eq!(answers.get(day!(8), 2), None);
+        assert_eq!(answers.get(day!(1), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "08", "part_1": 123 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(4), 2, "b");
+        answers.set(day!(2), 1, "a");
+        answers.set(day!(4), 2, "c");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(2));
+        assert_eq!(answers.get(day!(2), 1), Some("a"));
+        assert_eq!(answers.get(day!(4), 2), Some("c"));
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "line 1\n\"quoted\"");
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.get(day!(1), 1), Some("line 1\n\"quoted\""));
+    }
+}
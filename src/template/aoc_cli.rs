@@ -4,7 +4,8 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{Day, Year};
+use crate::template::submissions::SubmitVerdict;
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -33,22 +34,6 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
-
 pub fn download(day: Day) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
@@ -72,38 +57,61 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Downloads only the puzzle description, e.g. to fetch part two once it unlocked.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            get_puzzle_path(day),
+        ],
+        day,
+    );
+
+    call_aoc_cli(&args)
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<SubmitVerdict, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    match call_aoc_cli_captured(&args) {
+        Ok(output) => Ok(SubmitVerdict::from_response(&String::from_utf8_lossy(
+            &output.stdout,
+        ))),
+        Err(AocCommandError::BadExitStatus(output)) => {
+            // the response might still contain a verdict if aoc-cli reports it as an error.
+            match SubmitVerdict::from_response(&String::from_utf8_lossy(&output.stdout)) {
+                SubmitVerdict::Unknown(_) => Err(AocCommandError::BadExitStatus(output)),
+                verdict => Ok(verdict),
+            }
+        }
+        Err(e) => Err(e),
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+pub fn get_input_path(day: Day) -> String {
+    format!("{}/inputs/{day}.txt", Year::selected().data_dir().display())
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("{}/puzzles/{day}.md", Year::selected().data_dir().display())
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        Year::selected().to_string(),
+        "--day".into(),
+        day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
@@ -117,6 +125,24 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    into_result(output)
+}
+
+/// Like [`call_aoc_cli`], but keeps stdout in the returned [`Output`] so the response can be inspected.
+/// The captured stdout is echoed once the command exits.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    into_result(output)
+}
+
+fn into_result(output: Output) -> Result<Output, AocCommandError> {
     if output.status.success() {
         Ok(output)
     } else {
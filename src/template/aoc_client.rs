@@ -0,0 +1,399 @@
+/// Built-in client for adventofcode.com, used instead of aoc-cli when a session token is configured.
+use std::{env, fmt::Display, fs, path::PathBuf, time::Duration};
+
+use ureq::Agent;
+
+use crate::template::submissions::SubmitVerdict;
+use crate::template::{Day, Year, html};
+
+const BASE_URL: &str = "https://adventofcode.com";
+/// Sent when `AOC_USER_AGENT` is not set. Does not include a contact, which adventofcode.com asks automated tools for.
+const DEFAULT_USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+
+/// Name of the environment variable overriding the user agent, e.g. with a link to your repository and an email address.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+/// Name of the environment variable holding the session token.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Name of the session token file, shared with aoc-cli. Looked up in the home and config directories.
+pub const SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Http(ureq::Error),
+    /// The server answered with an unexpected status. Contains the status code and the response text.
+    Status(u16, String),
+    UnexpectedResponse(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token found. Set {SESSION_ENV} or store it in ~/{SESSION_FILE}."
+            ),
+            ClientError::Http(e) => write!(f, "request failed: {e}"),
+            ClientError::Status(status, text) => {
+                write!(f, "server responded with status {status}: {text}")
+            }
+            ClientError::UnexpectedResponse(reason) => {
+                write!(f, "unexpected response: {reason}")
+            }
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(value: ureq::Error) -> Self {
+        ClientError::Http(value)
+    }
+}
+
+/// A puzzle page, split into its parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    /// Title of the puzzle, e.g. `Secret Entrance`.
+    pub title: Option<String>,
+    /// Markdown description of each unlocked part.
+    pub parts: Vec<String>,
+    /// Accepted answers of the solved parts.
+    pub answers: Vec<String>,
+}
+
+impl Puzzle {
+    /// Parses a puzzle page.
+    pub fn from_html(page: &str) -> Result<Self, ClientError> {
+        let articles = html::articles(page);
+
+        if articles.is_empty() {
+            return Err(ClientError::UnexpectedResponse(
+                "no puzzle description found on page.".into(),
+            ));
+        }
+
+        let parts: Vec<String> = articles.iter().map(|a| html::to_markdown(a)).collect();
+
+        let title = parts
+            .first()
+            .and_then(|part| part.lines().next())
+            .and_then(|heading| heading.split_once(": "))
+            .map(|(_, title)| title.trim_end_matches(" ---").trim().to_string());
+
+        let answers = html::code_after(page, "Your puzzle answer was")
+            .into_iter()
+            .map(html::decode_entities)
+            .collect();
+
+        Ok(Self {
+            title,
+            parts,
+            answers,
+        })
+    }
+
+    /// The description of all unlocked parts as a single markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = self.parts.join("\n\n");
+        markdown.push('\n');
+        markdown
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub verdict: SubmitVerdict,
+    /// The text of the response.
+    pub message: String,
+}
+
+impl SubmitResponse {
+    pub fn from_html(page: &str) -> Result<Self, ClientError> {
+        let article = html::articles(page).into_iter().next().ok_or_else(|| {
+            ClientError::UnexpectedResponse("no message found in submit response.".into())
+        })?;
+
+        let message = html::to_text(article);
+
+        Ok(Self {
+            verdict: SubmitVerdict::from_response(&message),
+            message,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(user_agent())
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            year,
+        }
+    }
+
+    /// Creates a client for adventofcode.com from the configured session token and the selected year.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = find_session().ok_or(ClientError::MissingSession)?;
+        Ok(Self::new(BASE_URL, &session, Year::selected().into_inner()))
+    }
+
+    pub fn download_input(&self, day: Day) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    pub fn download_puzzle(&self, day: Day) -> Result<Puzzle, ClientError> {
+        Puzzle::from_html(&self.get(&self.day_url(day))?)
+    }
+
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitResponse, ClientError> {
+        let mut response = self
+            .agent
+            .post(format!("{}/answer", self.day_url(day)))
+            .header("Cookie", &self.cookie())
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])?;
+
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        if status != 200 {
+            return Err(ClientError::Status(status, html::to_text(&body)));
+        }
+
+        SubmitResponse::from_html(&body)
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Cookie", &self.cookie())
+            .call()?;
+
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        if status == 200 {
+            Ok(body)
+        } else {
+            Err(ClientError::Status(status, html::to_text(&body)))
+        }
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn user_agent() -> String {
+    env::var(USER_AGENT_ENV)
+        .ok()
+        .map(|agent| agent.trim().to_string())
+        .filter(|agent| !agent.is_empty())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string())
+}
+
+/// Reads the session token from `AOC_SESSION` or from a session file in the home or config directory.
+pub fn find_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV)
+        && !session.trim().is_empty()
+    {
+        return Some(session.trim().to_string());
+    }
+
+    session_file_paths()
+        .into_iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+}
+
+fn session_file_paths() -> Vec<PathBuf> {
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(SESSION_FILE)),
+        config.map(|config| config.join(SESSION_FILE.trim_start_matches('.'))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::{Client, ClientError, Puzzle};
+    use crate::day;
+    use crate::template::submissions::SubmitVerdict;
+
+    /// A request as received by the stand-in server.
+    struct Request {
+        head: String,
+        body: String,
+    }
+
+    /// Starts a stand-in server that answers a single request and returns a client pointing to it.
+    fn serve(status: u16, response: &'static str) -> (Client, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+
+            let content_length = head
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let response = format!(
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+
+            sender
+                .send(Request {
+                    head,
+                    body: String::from_utf8(body).unwrap(),
+                })
+                .unwrap();
+        });
+
+        (
+            Client::new(&format!("http://{address}"), "secret", 2025),
+            receiver,
+        )
+    }
+
+    const PUZZLE_PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>Find the <em>password</em>.</p></article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Again.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn downloads_input() {
+        let (client, requests) = serve(200, "1\n2\n3\n");
+
+        assert_eq!(client.download_input(day!(1)).unwrap(), "1\n2\n3\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.head.starts_with("GET /2025/day/1/input "));
+        assert!(request.head.contains("session=secret"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (client, requests) = serve(200, PUZZLE_PAGE);
+
+        let puzzle = client.download_puzzle(day!(12)).unwrap();
+        assert_eq!(puzzle.title.as_deref(), Some("Secret Entrance"));
+        assert_eq!(puzzle.parts.len(), 2);
+        assert_eq!(puzzle.answers, vec!["1234"]);
+        assert_eq!(
+            puzzle.to_markdown(),
+            "## --- Day 1: Secret Entrance ---\n\nFind the *password*.\n\n## --- Part Two ---\n\nAgain.\n"
+        );
+
+        assert!(
+            requests
+                .recv()
+                .unwrap()
+                .head
+                .starts_with("GET /2025/day/12 ")
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (client, requests) = serve(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high. <a href=\"/2025/day/1\">[Return to Day 1]</a></p></article></main>",
+        );
+
+        let response = client.submit(day!(1), 2, "42").unwrap();
+        assert_eq!(response.verdict, SubmitVerdict::TooHigh);
+        assert!(response.message.starts_with("That's not the right answer"));
+
+        let request = requests.recv().unwrap();
+        assert!(request.head.starts_with("POST /2025/day/1/answer "));
+        assert_eq!(request.body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (client, _) = serve(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+
+        match client.download_input(day!(1)) {
+            Err(ClientError::Status(400, text)) => {
+                assert_eq!(
+                    text,
+                    "Puzzle inputs differ by user. Please log in to get your puzzle input."
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_pages_without_puzzle() {
+        assert!(Puzzle::from_html("<html><body>404 Not Found</body></html>").is_err());
+    }
+}
@@ -0,0 +1,126 @@
+/// Common interface over the built-in client and aoc-cli.
+use std::{fmt::Display, fs, io};
+
+use crate::template::{Day, Year};
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::submissions::SubmitVerdict;
+use crate::template::aoc_client::{Client, ClientError, SESSION_ENV, SESSION_FILE};
+
+pub enum Backend {
+    Native(Client),
+    AocCli,
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    /// Neither a session token nor aoc-cli is available.
+    Unavailable,
+    Client(ClientError),
+    AocCli(AocCommandError),
+    Io(io::Error),
+}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::Unavailable => write!(
+                f,
+                "no session token found and command \"aoc\" not found or not callable. \
+                Set {SESSION_ENV}, store the token in ~/{SESSION_FILE} or run \"cargo install aoc-cli\"."
+            ),
+            BackendError::Client(e) => write!(f, "{e}"),
+            BackendError::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            BackendError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<ClientError> for BackendError {
+    fn from(value: ClientError) -> Self {
+        BackendError::Client(value)
+    }
+}
+
+impl From<AocCommandError> for BackendError {
+    fn from(value: AocCommandError) -> Self {
+        BackendError::AocCli(value)
+    }
+}
+
+impl From<io::Error> for BackendError {
+    fn from(value: io::Error) -> Self {
+        BackendError::Io(value)
+    }
+}
+
+impl Backend {
+    /// Uses the built-in client if a session token is configured and falls back to aoc-cli otherwise.
+    pub fn detect() -> Result<Self, BackendError> {
+        match Client::from_env() {
+            Ok(client) => Ok(Backend::Native(client)),
+            Err(ClientError::MissingSession) if aoc_cli::check().is_ok() => Ok(Backend::AocCli),
+            Err(ClientError::MissingSession) => Err(BackendError::Unavailable),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Native(_) => "adventofcode.com",
+            Backend::AocCli => "aoc-cli",
+        }
+    }
+
+    /// Writes the input and the puzzle description of a day to the data directory.
+    pub fn download(&self, day: Day) -> Result<(), BackendError> {
+        let data_dir = Year::selected().data_dir();
+        fs::create_dir_all(data_dir.join("inputs"))?;
+        fs::create_dir_all(data_dir.join("puzzles"))?;
+
+        match self {
+            Backend::Native(client) => {
+                let input_path = aoc_cli::get_input_path(day);
+                let puzzle_path = aoc_cli::get_puzzle_path(day);
+
+                fs::write(&input_path, client.download_input(day)?)?;
+                fs::write(&puzzle_path, client.download_puzzle(day)?.to_markdown())?;
+
+                println!("---");
+                println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+                println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+            }
+            Backend::AocCli => {
+                aoc_cli::download(day)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the puzzle description of a day to the data directory, e.g. once part two unlocked.
+    pub fn download_puzzle(&self, day: Day) -> Result<(), BackendError> {
+        match self {
+            Backend::Native(client) => {
+                fs::write(
+                    aoc_cli::get_puzzle_path(day),
+                    client.download_puzzle(day)?.to_markdown(),
+                )?;
+            }
+            Backend::AocCli => {
+                aoc_cli::download_puzzle(day)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Submits an answer and returns the verdict of the server.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, BackendError> {
+        match self {
+            Backend::Native(client) => {
+                let response = client.submit(day, part, answer)?;
+                println!("{}", response.message);
+                Ok(response.verdict)
+            }
+            Backend::AocCli => Ok(aoc_cli::submit(day, part, answer)?),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::alloc::format_bytes;
+use crate::template::heap_profile::{self, Profile, Site, SiteDiff};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub use crate::template::heap_profile::SortBy;
+
+/// Number of sites printed by default.
+pub const DEFAULT_TOP: usize = 10;
+
+/// Summarises a DHAT profile, or compares it against `old` if passed.
+pub fn handle(file: Option<&Path>, old: Option<&Path>, sort: SortBy, top: usize) {
+    let file = file.unwrap_or(Path::new(heap_profile::DEFAULT_FILE));
+
+    let result = match old {
+        Some(old) => print_diff(old, file, top),
+        None => print_summary(file, sort, top),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+/// Prints the totals of a profile and its `top` allocation sites.
+pub fn print_summary(file: &Path, sort: SortBy, top: usize) -> Result<(), String> {
+    let profile = Profile::read_from_file(file)?;
+    let total = profile.total();
+
+    println!("{ANSI_BOLD}Heap profile \"{}\"{ANSI_RESET}", file.display());
+    println!(
+        "Total: {} in {} blocks · {} at peak",
+        format_bytes(total.bytes),
+        total.blocks,
+        format_bytes(total.peak)
+    );
+    println!();
+    println!("{:>10}  {:>8}  {:>10}  Site", "Bytes", "Blocks", "At peak");
+
+    for site in profile.by_location(sort).iter().take(top) {
+        println!(
+            "{:>10}  {:>8}  {:>10}  {}",
+            format_bytes(site.bytes),
+            site.blocks,
+            format_bytes(site.peak),
+            describe(site)
+        );
+
+        if let Some(source) = site.frame.as_ref().and_then(|f| f.source_line()) {
+            println!("{:34}{ANSI_ITALIC}{source}{ANSI_RESET}", "");
+        }
+    }
+
+    Ok(())
+}
+
+fn print_diff(old_file: &Path, new_file: &Path, top: usize) -> Result<(), String> {
+    let old = Profile::read_from_file(old_file)?;
+    let new = Profile::read_from_file(new_file)?;
+    let (old_total, new_total) = (old.total(), new.total());
+
+    println!(
+        "{ANSI_BOLD}Heap profile \"{}\" → \"{}\"{ANSI_RESET}",
+        old_file.display(),
+        new_file.display()
+    );
+    println!(
+        "Bytes:   {}",
+        change(old_total.bytes, new_total.bytes, format_bytes)
+    );
+    println!(
+        "Blocks:  {}",
+        change(old_total.blocks, new_total.blocks, |x| x.to_string())
+    );
+    println!(
+        "At peak: {}",
+        change(old_total.peak, new_total.peak, format_bytes)
+    );
+    println!();
+
+    let diffs = heap_profile::diff(&old, &new);
+
+    if diffs.is_empty() {
+        println!("No function changed its allocations.");
+        return Ok(());
+    }
+
+    for diff in diffs.iter().take(top) {
+        let (old, new) = counts(diff);
+        println!("{}", describe_function(diff));
+        println!(
+            "    bytes {} · blocks {}",
+            change(old.bytes, new.bytes, format_bytes),
+            change(old.blocks, new.blocks, |x| x.to_string())
+        );
+    }
+
+    Ok(())
+}
+
+/// Formats a change like `1.0 KiB → 512 B (-50.0%)`.
+#[allow(clippy::cast_precision_loss)]
+fn change(old: u64, new: u64, format: impl Fn(u64) -> String) -> String {
+    let percent = if old == 0 {
+        String::new()
+    } else {
+        format!(" ({:+.1}%)", (new as f64 - old as f64) / old as f64 * 100_f64)
+    };
+
+    format!("{} → {}{percent}", format(old), format(new))
+}
+
+fn counts(diff: &SiteDiff) -> (Site, Site) {
+    let empty = Site {
+        frame: None,
+        bytes: 0,
+        blocks: 0,
+        peak: 0,
+    };
+
+    (
+        diff.old.clone().unwrap_or_else(|| empty.clone()),
+        diff.new.clone().unwrap_or(empty),
+    )
+}
+
+fn describe(site: &Site) -> String {
+    match &site.frame {
+        Some(frame) => format!("{} ({})", frame.location(), frame.function),
+        None => "unknown".into(),
+    }
+}
+
+fn describe_function(diff: &SiteDiff) -> String {
+    match &diff.frame {
+        Some(frame) => format!("{} ({})", frame.function, frame.file),
+        None => "unknown".into(),
+    }
+}
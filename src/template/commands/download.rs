@@ -1,14 +1,18 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::Day;
+use crate::template::backend::Backend;
+use crate::template::examples;
 use std::process;
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let backend = Backend::detect().unwrap_or_else(|e| {
+        eprintln!("{e}");
         process::exit(1);
-    }
+    });
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = backend.download(day) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
+
+    examples::update(day);
 }
@@ -1,6 +1,11 @@
 pub mod all;
+pub mod dhat;
 pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
+pub mod watch;
@@ -1,15 +1,101 @@
+use std::fs;
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::backend::Backend;
+use crate::template::{Day, examples, terminal};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// The part of a puzzle description to show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Section {
+    All,
+    /// Only the description of part 1 or 2.
+    Part(u8),
+    /// Only the example inputs.
+    Examples,
+}
+
+/// Renders the puzzle description of a day in the terminal.
+/// The description is downloaded if it is not present yet or if `refresh` is set, e.g. after part two unlocked.
+pub fn handle(day: Day, section: Section, refresh: bool) {
+    let puzzle_path = get_puzzle_path(day);
+
+    if refresh || fs::metadata(&puzzle_path).is_err() {
+        let backend = Backend::detect().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+        if let Err(e) = backend.download_puzzle(day) {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        };
+
+        examples::update(day);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let markdown = fs::read_to_string(&puzzle_path).unwrap_or_else(|e| {
+        eprintln!("failed to read \"{puzzle_path}\": {e}");
+        process::exit(1);
+    });
+
+    let Some(markdown) = select(&markdown, section) else {
+        eprintln!("Day {day} has no {}.", describe(section));
         process::exit(1);
     };
+
+    let (columns, _) = terminal::size();
+    terminal::page(&terminal::render(&markdown, columns));
+}
+
+/// The markdown of a section, `None` if it is not part of the description.
+fn select(markdown: &str, section: Section) -> Option<String> {
+    match section {
+        Section::All => Some(markdown.to_string()),
+        Section::Part(part) => examples::split_parts(markdown)
+            .get(usize::from(part).checked_sub(1)?)
+            .map(|part| part.to_string()),
+        Section::Examples => {
+            let blocks: Vec<String> = examples::extract(markdown)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, part)| {
+                    Some(format!("## Example of part {}\n\n```\n{}```", i + 1, part.input?))
+                })
+                .collect();
+
+            (!blocks.is_empty()).then(|| blocks.join("\n\n"))
+        }
+    }
+}
+
+fn describe(section: Section) -> String {
+    match section {
+        Section::All => "description".into(),
+        Section::Part(part) => format!("part {part} yet"),
+        Section::Examples => "examples".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Section, select};
+
+    const PUZZLE: &str = "## --- Day 1: Test ---\n\nFor example:\n\n```\n1\n```\n\n## --- Part Two ---\n\nAgain.\n";
+
+    #[test]
+    fn selects_sections() {
+        assert_eq!(select(PUZZLE, Section::All).as_deref(), Some(PUZZLE));
+        assert_eq!(
+            select(PUZZLE, Section::Part(2)).as_deref(),
+            Some("## --- Part Two ---\n\nAgain.\n")
+        );
+        assert_eq!(
+            select(PUZZLE, Section::Examples).as_deref(),
+            Some("## Example of part 1\n\n```\n1\n```")
+        );
+        assert_eq!(select("## --- Day 1: Test ---\n", Section::Part(2)), None);
+    }
 }
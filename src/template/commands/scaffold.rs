@@ -1,13 +1,127 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::examples::{self, answer_expr};
+use crate::template::{Day, Year};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Template used if neither `--template` nor `AOC_TEMPLATE` select one and `templates/default.txt` does not exist.
+const DEFAULT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
+
+/// Directory holding the named templates, e.g. `templates/grid.txt`.
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// Name of the environment variable selecting the default template.
+pub const TEMPLATE_ENV: &str = "AOC_TEMPLATE";
+
+/// Values that are filled into the `%PLACEHOLDERS%` of a template.
+#[derive(Clone, Debug)]
+pub struct TemplateContext {
+    pub year: Year,
+    pub day: Day,
+    /// Title of the puzzle, if its description was downloaded already.
+    pub title: Option<String>,
+    /// Expected answers of the example input for part one and two.
+    pub examples: [Option<String>; 2],
+}
+
+impl TemplateContext {
+    pub fn new(year: Year, day: Day) -> Self {
+        Self {
+            year,
+            day,
+            title: None,
+            examples: [None, None],
+        }
+    }
+
+    /// Replaces all placeholders of `template`:
+    ///  - `%DAY_NUMBER%`: the day without leading zero, e.g. `5`.
+    ///  - `%DAY%`: the day with leading zero, e.g. `05`.
+    ///  - `%YEAR%`: the year, e.g. `2025`.
+    ///  - `%TITLE%`: the puzzle title, e.g. `Secret Entrance`. Falls back to `Day 5` if it is not known.
+    ///  - `%PART_ONE_EXAMPLE%`, `%PART_TWO_EXAMPLE%`: the expected example answer as a rust expression, e.g. `Some(42)`.
+    pub fn render(&self, template: &str) -> String {
+        let title = self
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {}", self.day.into_inner()));
+
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace("%DAY%", &self.day.to_string())
+            .replace("%YEAR%", &self.year.to_string())
+            .replace("%TITLE%", &title)
+            .replace(
+                "%PART_ONE_EXAMPLE%",
+                &answer_expr(self.examples[0].as_deref()),
+            )
+            .replace(
+                "%PART_TWO_EXAMPLE%",
+                &answer_expr(self.examples[1].as_deref()),
+            )
+    }
+}
+
+/// Reads a template by name from the templates directory, or from a path if `name` is one.
+fn load_template(name: &str) -> Result<String, String> {
+    let path = if name.contains(['/', '\\']) || name.ends_with(".txt") {
+        Path::new(name).to_path_buf()
+    } else {
+        Path::new(TEMPLATES_DIR).join(format!("{name}.txt"))
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == ErrorKind::NotFound && name == "default" => {
+            Ok(DEFAULT_TEMPLATE.to_string())
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Err(format!(
+            "template \"{name}\" not found. Available templates: {}.",
+            available_templates().join(", ")
+        )),
+        Err(e) => Err(format!(
+            "failed to read template \"{}\": {e}",
+            path.display()
+        )),
+    }
+}
+
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !names.iter().any(|name| name == "default") {
+        names.push("default".into());
+    }
+
+    names.sort();
+    names
+}
+
+/// Reads the title from a downloaded puzzle description, e.g. `## --- Day 1: Secret Entrance ---`.
+pub fn puzzle_title(day: Day) -> Option<String> {
+    let puzzle = fs::read_to_string(get_puzzle_path(day)).ok()?;
+    let heading = puzzle.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().trim_end_matches('-').trim().to_string())
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,18 +133,47 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file unless it exists already, e.g. because the input was downloaded before.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    match safe_create_file(path, false) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Scaffolds a day from a template.
+/// The template is selected by `template`, then by `AOC_TEMPLATE`, and defaults to `default`.
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>) {
+    let year = Year::selected();
+    let data_dir = year.data_dir();
+    let input_path = format!("{}/inputs/{day}.txt", data_dir.display());
+    let example_path = format!("{}/examples/{day}.txt", data_dir.display());
+    let module_path = year.bin_path(day);
+
+    let template_name = template
+        .map(ToString::to_string)
+        .or_else(|| env::var(TEMPLATE_ENV).ok().filter(|name| !name.is_empty()))
+        .unwrap_or_else(|| "default".into());
+
+    let template = load_template(&template_name).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let parts = examples::from_puzzle(day);
+    let context = TemplateContext {
+        title: puzzle_title(day),
+        examples: [0, 1].map(|i| parts.get(i).and_then(|part| part.answer.clone())),
+        ..TemplateContext::new(year, day)
+    };
+
+    for folder in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(data_dir.join(folder)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +183,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(context.render(&template).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -55,9 +194,10 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
@@ -65,15 +205,56 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&example_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    // fills the examples if the puzzle was downloaded before.
+    examples::update(day);
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DEFAULT_TEMPLATE, TemplateContext};
+    use crate::day;
+    use crate::template::Year;
+
+    #[test]
+    fn renders_placeholders() {
+        let context = TemplateContext {
+            title: Some("Secret Entrance".into()),
+            examples: [Some("42".into()), Some("abc".into())],
+            ..TemplateContext::new(Year::new(2024).unwrap(), day!(5))
+        };
+
+        assert_eq!(
+            context
+                .render("%YEAR%-%DAY% %DAY_NUMBER%: %TITLE% %PART_ONE_EXAMPLE% %PART_TWO_EXAMPLE%"),
+            "2024-05 5: Secret Entrance Some(42) Some(\"abc\")"
+        );
+    }
+
+    #[test]
+    fn renders_defaults() {
+        let context = TemplateContext::new(Year::new(2025).unwrap(), day!(12));
+        assert_eq!(
+            context.render("%TITLE%: %PART_TWO_EXAMPLE%"),
+            "Day 12: None"
+        );
+
+        let module = context.render(DEFAULT_TEMPLATE);
+        assert!(module.starts_with("advent_of_code::solution!(12);"));
+        assert!(!module.contains('%'));
+    }
+}
@@ -1,9 +1,16 @@
-use std::process::{Command, Stdio};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::commands::dhat::{self, SortBy};
+use crate::template::heap_profile;
+use crate::template::{Day, Year, alloc};
 
 pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        Year::selected().bin_name(day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +23,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if !dhat && alloc::ENABLED {
+        cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -30,5 +41,18 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // forward the exit code, e.g. the verdict of a submission.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+
+    if dhat {
+        println!();
+        let file = Path::new(heap_profile::DEFAULT_FILE);
+        if let Err(e) = dhat::print_summary(file, SortBy::Bytes, dhat::DEFAULT_TOP) {
+            eprintln!("Failed to summarise heap profile: {e}");
+        }
+    }
 }
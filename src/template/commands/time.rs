@@ -1,15 +1,35 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::history::{self, HistoryEntry};
 use crate::template::run_multi::run_multi;
+use crate::template::runner::BenchConfig;
+use crate::template::stats::format_nanos;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, readme_benchmarks, Day, Execution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Regression threshold in percent used by `--compare` if `--threshold` is not set.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Benches the selected days. If `compare` holds a threshold in percent, the results are compared against
+/// the stored timings and the process exits with a non-zero code if any day regressed past it.
+/// Days are benched one after another unless `jobs` is explicitly set above 1.
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<f64>,
+    bench: &BenchConfig,
+    execution: Execution,
+    jobs: usize,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || compare.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,9 +41,20 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if jobs > 1 {
+        eprintln!("Warning: benching {jobs} days in parallel, timings may influence each other.");
+    }
+
+    let timings = run_multi(&days_to_run, execution, Some(bench), jobs)
+        .timings
+        .unwrap();
+
+    let regressions =
+        compare.map(|threshold| print_comparison(&stored_timings, &timings, threshold));
 
     if store {
+        let commit = history::head_commit();
+        let timings = timings.with_history(history::now(), commit.as_deref());
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
@@ -37,4 +68,91 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if regressions.is_some_and(|count| count > 0) {
+        process::exit(1);
+    }
+}
+
+/// Prints the per-day delta against the stored timings and returns the number of regressed days.
+fn print_comparison(stored: &Timings, timings: &Timings, threshold: f64) -> usize {
+    let deltas = stored.compare(timings);
+
+    println!("\n{ANSI_BOLD}Comparison with stored timings{ANSI_RESET}");
+
+    for delta in &deltas {
+        match (delta.stored_nanos, delta.percent()) {
+            (Some(stored_nanos), Some(percent)) => {
+                let marker = if delta.is_regression(threshold) {
+                    " ✖ regression"
+                } else {
+                    ""
+                };
+                println!(
+                    "Day {}: {} → {} ({percent:+.1}%){marker}",
+                    delta.day,
+                    format_nanos(stored_nanos),
+                    format_nanos(delta.new_nanos),
+                );
+            }
+            _ => println!("Day {}: no stored timing.", delta.day),
+        }
+    }
+
+    let regressions = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+
+    if regressions == 0 {
+        println!("No day regressed by more than {threshold}%.");
+    } else {
+        eprintln!("{regressions} day(s) regressed by more than {threshold}%.");
+    }
+
+    regressions
+}
+
+/// Prints all stored timings of a day, oldest first, with the change relative to the previous run.
+pub fn handle_history(day: Day) {
+    let stored_timings = Timings::read_from_file();
+    let entries = stored_timings.history_for(day);
+
+    if entries.is_empty() {
+        println!("No stored timings for day {day}. Run `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!(
+        "{:<16}  {:<8}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "Date", "Commit", "Parse", "Part 1", "Part 2", "Total", "Change"
+    );
+
+    let mut previous: Option<&HistoryEntry> = None;
+
+    for entry in entries {
+        let date = entry
+            .timestamp
+            .map_or_else(|| "unknown".into(), history::format_timestamp);
+        let commit = entry
+            .commit
+            .as_deref()
+            .map_or("-", |c| &c[..c.len().min(8)]);
+        let format_part = |nanos: Option<f64>| nanos.map_or_else(|| "-".into(), format_nanos);
+
+        let change = previous
+            .map(HistoryEntry::total_nanos)
+            .filter(|prev| *prev > 0_f64)
+            .map_or_else(String::new, |prev| {
+                format!("{:+.1}%", (entry.total_nanos() - prev) / prev * 100_f64)
+            });
+
+        println!(
+            "{date:<16}  {commit:<8}  {:>10}  {:>10}  {:>10}  {:>10}  {ANSI_ITALIC}{change:>8}{ANSI_RESET}",
+            format_part(entry.parse_nanos),
+            format_part(entry.part_1_nanos),
+            format_part(entry.part_2_nanos),
+            format_nanos(entry.total_nanos()),
+        );
+
+        previous = Some(entry);
+    }
 }
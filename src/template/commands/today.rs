@@ -0,0 +1,290 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::template::backend::Backend;
+use crate::template::commands::read::{self, Section};
+use crate::template::commands::scaffold;
+use crate::template::{AllDays, Day, Year};
+
+/// Interval between two updates of the countdown.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Number of download attempts after a puzzle unlocked.
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+
+/// Delay before the first retry, doubled after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Source of the current time. Injected so the waiting logic can be tested without actually waiting.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Downloads, scaffolds and reads the puzzle of the current day.
+/// With `wait`, blocks until the next puzzle unlocks instead of failing outside of the advent calendar.
+pub fn handle(wait: bool) {
+    let clock = SystemClock;
+    let year = Year::selected();
+
+    let day = if wait {
+        let Some((day, unlock)) =
+            next_day(&clock, year, |day| Path::new(&year.bin_path(day)).exists())
+        else {
+            eprintln!(
+                "All puzzles of {year} are unlocked already. Please use `scaffold` with a specific day."
+            );
+            process::exit(1);
+        };
+
+        wait_until(&clock, unlock, |remaining| {
+            print!("\r⏳ Day {day} unlocks in {}", format_countdown(remaining));
+            io::stdout().flush().ok();
+        });
+        println!("\r🎄 Day {day} is unlocked!{}", " ".repeat(16));
+
+        day
+    } else {
+        Day::today().unwrap_or_else(|| {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the {}th of december {year}. Use `--wait` to wait for the next puzzle \
+                or `scaffold` with a specific day.",
+                year.days()
+            );
+            process::exit(1)
+        })
+    };
+
+    let backend = Backend::detect().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = retry(&clock, || backend.download(day)) {
+        eprintln!("failed to download day {day}: {e}");
+        process::exit(1);
+    }
+
+    // scaffold after downloading, so the template can use the title of the puzzle.
+    scaffold::handle(day, false, None);
+    read::handle(day, Section::All, false);
+}
+
+/// The day to work on and the time its puzzle unlocks.
+/// This is the current day if it was not scaffolded yet, the next day of the calendar to unlock otherwise.
+fn next_day(
+    clock: &impl Clock,
+    year: Year,
+    is_scaffolded: impl Fn(Day) -> bool,
+) -> Option<(Day, DateTime<Utc>)> {
+    let now = clock.now();
+
+    if let Some(today) = Day::on(now, year)
+        && !is_scaffolded(today)
+    {
+        return Some((today, today.unlocks_at(year)));
+    }
+
+    AllDays::of(year)
+        .map(|day| (day, day.unlocks_at(year)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+/// Blocks until `time` is reached, calling `on_tick` with the remaining time about once per second.
+fn wait_until(clock: &impl Clock, time: DateTime<Utc>, mut on_tick: impl FnMut(Duration)) {
+    while let Ok(remaining) = (time - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+        on_tick(remaining);
+        clock.sleep(remaining.min(TICK));
+    }
+}
+
+/// Calls `f` until it succeeds, backing off exponentially between attempts.
+/// Returns the last error if all attempts fail.
+fn retry<T, E: Display>(clock: &impl Clock, mut f: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    let mut attempt = 1;
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                eprintln!(
+                    "attempt {attempt} of {DOWNLOAD_ATTEMPTS} failed: {e}. Retrying in {}s.",
+                    backoff.as_secs()
+                );
+                clock.sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Formats a duration like `2d 03:04:05`, omitting the days if there are none.
+fn format_countdown(duration: Duration) -> String {
+    // round up, so the countdown shows `00:00:00` only at the unlock.
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{Clock, format_countdown, next_day, retry, wait_until};
+    use crate::day;
+    use crate::template::Year;
+
+    /// A clock that only advances when slept on.
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Self {
+            Self {
+                now: Cell::new(
+                    Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+                        .unwrap(),
+                ),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.sleeps.borrow_mut().push(duration);
+            self.now
+                .set(self.now.get() + chrono::Duration::from_std(duration).unwrap());
+        }
+    }
+
+    fn year() -> Year {
+        Year::new(2025).unwrap()
+    }
+
+    #[test]
+    fn waits_for_first_day() {
+        // 2025-11-30 23:59:58 in UTC-5.
+        let clock = FakeClock::at(2025, 12, 1, 4, 59, 58);
+        let (day, unlock) = next_day(&clock, year(), |_| false).unwrap();
+
+        assert_eq!(day, day!(1));
+        assert_eq!(unlock, Utc.with_ymd_and_hms(2025, 12, 1, 5, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn picks_today_if_not_scaffolded() {
+        let clock = FakeClock::at(2025, 12, 3, 12, 0, 0);
+
+        assert_eq!(next_day(&clock, year(), |_| false).unwrap().0, day!(3));
+        assert_eq!(
+            next_day(&clock, year(), |day| day == day!(3)).unwrap().0,
+            day!(4)
+        );
+    }
+
+    #[test]
+    fn stops_after_last_day() {
+        let clock = FakeClock::at(2025, 12, 13, 6, 0, 0);
+        assert_eq!(next_day(&clock, year(), |_| true), None);
+
+        let clock = FakeClock::at(2026, 1, 1, 0, 0, 0);
+        assert_eq!(next_day(&clock, year(), |_| false), None);
+    }
+
+    #[test]
+    fn counts_down_to_unlock() {
+        let clock = FakeClock::at(2025, 12, 1, 4, 59, 57);
+        let unlock = day!(1).unlocks_at(year());
+        let mut ticks = vec![];
+
+        wait_until(&clock, unlock, |remaining| {
+            ticks.push(format_countdown(remaining))
+        });
+
+        assert_eq!(ticks, vec!["00:00:03", "00:00:02", "00:00:01"]);
+        assert_eq!(clock.now(), unlock);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(1500)), "00:00:02");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 3600 + 61)),
+            "03:01:01"
+        );
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86_400 + 5)),
+            "2d 00:00:05"
+        );
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(2025, 12, 1, 5, 0, 0);
+        let mut calls = 0;
+
+        let result = retry(&clock, || {
+            calls += 1;
+            if calls < 3 { Err("not yet") } else { Ok(calls) }
+        });
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![Duration::from_secs(2), Duration::from_secs(4)]
+        );
+    }
+
+    #[test]
+    fn gives_up_after_last_attempt() {
+        let clock = FakeClock::at(2025, 12, 1, 5, 0, 0);
+        let result: Result<(), _> = retry(&clock, || Err("unavailable"));
+
+        assert_eq!(result, Err("unavailable"));
+        assert_eq!(clock.sleeps.borrow().len(), 5);
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::record::PartRecord;
+use crate::template::run_multi::run_multi;
+use crate::template::{all_days, Day, Execution, ANSI_BOLD, ANSI_RESET};
+
+/// Result of checking a single part against the known answers.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Match,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The part has no known answer yet.
+    Missing,
+    /// The part did not produce an answer and has no known answer either.
+    Unsolved,
+    /// The part did not produce an answer, but has a known answer.
+    Lost {
+        expected: String,
+    },
+    /// The part returned an error.
+    Failed(String),
+}
+
+/// Runs the selected days and checks every part against `data/<year>/answers.json`.
+/// With `accept`, the answers of this run are stored as the new known answers instead.
+/// Exits with a non-zero code if any part with a known answer produced a different answer, no answer or no result at all, or if any part returned an error.
+pub fn handle(day: Option<Day>, accept: bool, execution: Execution, jobs: usize) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let mut answers = Answers::read_from_file();
+    let records = run_multi(&days_to_run, execution, None, jobs).records;
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+
+    let mut mismatches = 0;
+
+    for record in records.iter().filter(|r| !r.is_parse()) {
+        let label = format!("Day {} Part {}", record.day, record.part);
+
+        match verdict(&answers, record) {
+            Verdict::Match => println!("{label}: ✔ match"),
+            Verdict::Mismatch { expected, actual } => {
+                mismatches += 1;
+                println!("{label}: ✖ mismatch (expected {expected}, got {actual})");
+            }
+            Verdict::Missing => println!("{label}: no known answer"),
+            Verdict::Unsolved => println!("{label}: not solved"),
+            Verdict::Lost { expected } => {
+                mismatches += 1;
+                println!("{label}: ✖ not solved (expected {expected})");
+            }
+            Verdict::Failed(error) => {
+                mismatches += 1;
+                println!("{label}: ⚠ failed ({error})");
+            }
+        }
+
+        if let Some(answer) = record.answer.as_ref().filter(|_| accept) {
+            answers.set(record.day, record.part, answer);
+        }
+    }
+
+    // a day that panicked produces no records at all.
+    for (day, part) in missing_parts(&answers, &days_to_run, &records) {
+        mismatches += 1;
+        println!("Day {day} Part {part}: ✖ no result (panicked?)");
+    }
+
+    if accept {
+        match answers.store_file() {
+            Ok(()) => println!("\nStored known answers."),
+            Err(e) => {
+                eprintln!("Failed to store known answers: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if mismatches > 0 {
+        eprintln!("\n{mismatches} part(s) failed or did not match the known answers.");
+        process::exit(1);
+    }
+}
+
+fn verdict(answers: &Answers, record: &PartRecord) -> Verdict {
+    if let Some(error) = &record.error {
+        return Verdict::Failed(error.clone());
+    }
+
+    let Some(actual) = &record.answer else {
+        return match answers.get(record.day, record.part) {
+            Some(expected) => Verdict::Lost {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unsolved,
+        };
+    };
+
+    match answers.get(record.day, record.part) {
+        Some(expected) if expected == actual => Verdict::Match,
+        Some(expected) => Verdict::Mismatch {
+            expected: expected.to_string(),
+            actual: actual.clone(),
+        },
+        None => Verdict::Missing,
+    }
+}
+
+/// Parts with a known answer that did not produce a record.
+fn missing_parts(answers: &Answers, days: &HashSet<Day>, records: &[PartRecord]) -> Vec<(Day, u8)> {
+    let mut days: Vec<Day> = days.iter().copied().collect();
+    days.sort();
+
+    days.into_iter()
+        .flat_map(|day| [(day, 1), (day, 2)])
+        .filter(|&(day, part)| answers.get(day, part).is_some())
+        .filter(|&(day, part)| !records.iter().any(|r| r.day == day && r.part == part))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{missing_parts, verdict, Verdict};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::record::PartRecord;
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    fn record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord::new(
+            day!(1),
+            part,
+            answer.map(ToString::to_string),
+            &Duration::ZERO,
+            None,
+        )
+    }
+
+    #[test]
+    fn checks_against_known_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+
+        assert_eq!(verdict(&answers, &record(1, Some("42"))), Verdict::Match);
+        assert_eq!(
+            verdict(&answers, &record(1, Some("41"))),
+            Verdict::Mismatch {
+                expected: "42".into(),
+                actual: "41".into()
+            }
+        );
+        assert_eq!(verdict(&answers, &record(2, Some("7"))), Verdict::Missing);
+        assert_eq!(verdict(&answers, &record(2, None)), Verdict::Unsolved);
+
+        let failed = PartRecord::failed(day!(1), 1, "bad input".into(), &Duration::ZERO, None);
+        assert_eq!(
+            verdict(&answers, &failed),
+            Verdict::Failed("bad input".into())
+        );
+    }
+
+    #[test]
+    fn fails_parts_losing_their_answer() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+
+        assert_eq!(
+            verdict(&answers, &record(1, None)),
+            Verdict::Lost {
+                expected: "42".into()
+            }
+        );
+    }
+
+    #[test]
+    fn finds_parts_without_records() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(1), 2, "43");
+        answers.set(day!(2), 1, "7");
+        answers.set(day!(3), 1, "8");
+
+        let days = HashSet::from([day!(1), day!(2)]);
+        let records = [record(1, Some("42"))];
+
+        assert_eq!(
+            missing_parts(&answers, &days, &records),
+            [(day!(1), 2), (day!(2), 1)]
+        );
+    }
+}
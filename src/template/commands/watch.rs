@@ -0,0 +1,234 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Time the files have to stay unchanged before the action runs, so that a burst of saves triggers one run.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// What to run when a watched file changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Run the tests of the day.
+    Test,
+    /// Run the solution against the puzzle input.
+    Solve,
+    /// Run the solution against the example input.
+    Example,
+}
+
+impl Action {
+    fn describe(self) -> &'static str {
+        match self {
+            Action::Test => "tests",
+            Action::Solve => "solution",
+            Action::Example => "example",
+        }
+    }
+
+    fn command(self, year: Year, day: Day) -> Command {
+        let bin = year.bin_name(day);
+        let mut cmd = Command::new("cargo");
+
+        // output is captured, so colors have to be requested explicitly.
+        match self {
+            Action::Test => cmd.args([
+                "test", "--color", "always", "--bin", &bin, "--", "--color", "always",
+            ]),
+            Action::Solve => cmd.args(["run", "--quiet", "--color", "always", "--bin", &bin]),
+            Action::Example => cmd.args([
+                "run",
+                "--quiet",
+                "--color",
+                "always",
+                "--bin",
+                &bin,
+                "--",
+                "--example",
+            ]),
+        };
+
+        cmd
+    }
+}
+
+/// Modification times of the watched files. Files that do not exist are not part of the snapshot.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    fn take(paths: &[PathBuf]) -> Self {
+        Self(
+            paths
+                .iter()
+                .filter_map(|path| Some((path.clone(), fs::metadata(path).ok()?.modified().ok()?)))
+                .collect(),
+        )
+    }
+}
+
+/// The module of the day, `src/lib.rs` and the files of the day in the data directory, e.g. `data/2025/examples/07-2.txt`.
+fn watched_files(year: Year, day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(year.bin_path(day)),
+        PathBuf::from("src/lib.rs"),
+    ];
+
+    for folder in ["inputs", "examples", "puzzles"] {
+        paths.extend(day_files(&year.data_dir().join(folder), day));
+    }
+
+    paths
+}
+
+fn day_files(dir: &Path, day: Day) -> Vec<PathBuf> {
+    let prefixes = [format!("{day}."), format!("{day}-")];
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| prefixes.iter().any(|prefix| name.starts_with(prefix)))
+        })
+        .collect();
+
+    paths.sort();
+    paths
+}
+
+/// Reruns `action` whenever the files of a day change. Runs until interrupted.
+pub fn handle(day: Day, action: Action) {
+    let year = Year::selected();
+    let mut snapshot = Snapshot::take(&watched_files(year, day));
+
+    run(year, day, action);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let mut current = Snapshot::take(&watched_files(year, day));
+        if current == snapshot {
+            continue;
+        }
+
+        // wait for the changes to settle, editors often write files in several steps.
+        loop {
+            thread::sleep(DEBOUNCE);
+            let next = Snapshot::take(&watched_files(year, day));
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+
+        snapshot = current;
+        run(year, day, action);
+    }
+}
+
+fn run(year: Year, day: Day, action: Action) {
+    print!("{ANSI_CLEAR_SCREEN}");
+    print!("⏳ Day {day} · running {}...", action.describe());
+    let _ = io::stdout().flush();
+
+    let timer = Instant::now();
+    let output = action.command(year, day).output();
+    let elapsed = timer.elapsed();
+
+    print!("{ANSI_CLEAR_SCREEN}");
+
+    match output {
+        Ok(output) => {
+            let (marker, verdict) = if output.status.success() {
+                ("✔", "passed")
+            } else {
+                ("✖", "failed")
+            };
+
+            println!(
+                "{ANSI_BOLD}{marker} Day {day} · {} {verdict}{ANSI_RESET} {ANSI_ITALIC}({elapsed:.1?}){ANSI_RESET}",
+                action.describe()
+            );
+            println!();
+
+            let _ = io::stdout().write_all(&output.stderr);
+            let _ = io::stdout().write_all(&output.stdout);
+        }
+        Err(e) => {
+            println!("{ANSI_BOLD}✖ Day {day} · failed to run cargo: {e}{ANSI_RESET}");
+        }
+    }
+
+    println!();
+    println!("{ANSI_ITALIC}Watching for changes, press Ctrl-C to stop.{ANSI_RESET}");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, thread, time::Duration};
+
+    use super::{Snapshot, day_files};
+    use crate::day;
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("07.txt");
+        let paths = vec![file.clone()];
+
+        let missing = Snapshot::take(&paths);
+        fs::write(&file, "1").unwrap();
+        let created = Snapshot::take(&paths);
+        assert_ne!(missing, created);
+        assert_eq!(created, Snapshot::take(&paths));
+
+        // make sure the modification time differs on file systems with coarse timestamps.
+        thread::sleep(Duration::from_millis(20));
+        fs::write(&file, "2").unwrap();
+        let file_time = fs::metadata(&file).unwrap().modified().unwrap();
+        let modified = Snapshot::take(&paths);
+        assert_eq!(modified.0.get(&file), Some(&file_time));
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(Snapshot::take(&paths), missing);
+    }
+
+    #[test]
+    fn selects_files_of_day() {
+        let dir = env::temp_dir().join(format!("aoc-watch-files-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["07.txt", "07-2.txt", "17.txt", "08.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let files = day_files(&dir, day!(7));
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["07-2.txt", "07.txt"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
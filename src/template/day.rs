@@ -2,13 +2,18 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 12).
+/// Length of the longest advent calendar.
+pub const MAX_DAYS: u8 = 25;
+
+/// A valid day number of advent (i.e. an integer in range 1 to the calendar length of the selected [`Year`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,10 +27,16 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] from the provided value if it's in the calendar of the selected year,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 12 {
+        Self::new_in(day, Year::selected())
+    }
+
+    /// Creates a [`Day`] from the provided value if it's in the calendar of `year`,
+    /// returns [`None`] otherwise.
+    pub fn new_in(day: u8, year: Year) -> Option<Self> {
+        if day == 0 || day > year.days() {
             return None;
         }
         Some(Self(day))
@@ -45,16 +56,33 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 12th of december, `None` otherwise.
+    /// Returns the current day if it's part of the advent calendar of the selected year, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 12 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        Self::on(Utc::now(), Year::selected())
+    }
+
+    /// Returns the day of `year` whose puzzle was unlocked last at `time`, `None` if `time` is not in its advent calendar.
+    pub fn on(time: DateTime<Utc>, year: Year) -> Option<Self> {
+        let today = time.with_timezone(&server_offset());
+        if today.month() == 12 && i32::from(year.into_inner()) == today.year() {
+            Self::new_in(u8::try_from(today.day()).ok()?, year)
         } else {
             None
         }
     }
+
+    /// The time the puzzle of this day unlocks in `year`, i.e. midnight in the timezone of the server.
+    pub fn unlocks_at(self, year: Year) -> DateTime<Utc> {
+        server_offset()
+            .with_ymd_and_hms(year.into_inner().into(), 12, self.0.into(), 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+}
+
+#[cfg(feature = "today")]
+fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
 }
 
 impl Display for Day {
@@ -94,26 +122,39 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 12")
+        write!(
+            f,
+            "expecting a day number between 1 and {}",
+            Year::selected().days()
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 12th.
+/// An iterator that yields every day of advent in the selected year.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 12th.
+/// An iterator that yields every day of advent from the 1st to the last day of the calendar.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::of(Year::selected())
+    }
+
+    /// Yields every day of advent in `year`.
+    pub fn of(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.days(),
+        }
     }
 }
 
@@ -121,10 +162,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 12 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 12.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the calendar length.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,15 +176,16 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// The day is checked against the longest calendar, see [`MAX_DAYS`].
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 12,
+            $day != 0 && $day <= $crate::template::MAX_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 12"
+                "`, expecting a value between 1 and 25"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,11 +196,12 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{AllDays, Day};
+    use crate::template::Year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::of(Year::new(2025).unwrap());
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -174,6 +217,22 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(12)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_of_long_calendar() {
+        let days: Vec<Day> = AllDays::of(Year::new(2024).unwrap()).collect();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days.last(), Some(&Day(25)));
+    }
+
+    #[test]
+    fn validates_days_against_year() {
+        let year = Year::new(2025).unwrap();
+        assert_eq!(Day::new_in(12, year), Some(Day(12)));
+        assert_eq!(Day::new_in(13, year), None);
+        assert_eq!(Day::new_in(0, year), None);
+        assert_eq!(Day::new_in(25, Year::new(2024).unwrap()), Some(Day(25)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,372 @@
+//! Extracts example inputs and their expected answers from downloaded puzzle descriptions.
+//! Puzzles introduce their example in a code block after a sentence like "For example:" and highlight
+//! the answer for it as emphasized code, usually as the last highlight of the part.
+use std::{fs, path::Path};
+
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::{Day, Year};
+
+/// Example input and expected answer of a single part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    /// Example input, if the part introduces a new one.
+    pub input: Option<String>,
+    /// Expected answer for the example.
+    pub answer: Option<String>,
+}
+
+/// Extracts the example of every unlocked part from a puzzle description in markdown.
+pub fn extract(markdown: &str) -> Vec<PartExample> {
+    let mut parts: Vec<PartExample> = vec![];
+
+    for (i, part) in split_parts(markdown).into_iter().enumerate() {
+        let blocks = blocks(part);
+        let input = example_input(&blocks, i == 0).filter(|input| {
+            parts
+                .first()
+                .is_none_or(|first| first.input.as_ref() != Some(input))
+        });
+
+        parts.push(PartExample {
+            input,
+            answer: last_highlight(&blocks),
+        });
+    }
+
+    parts
+}
+
+/// Extracts the examples from the downloaded puzzle description of a day.
+/// Returns no examples if the description was not downloaded yet.
+pub fn from_puzzle(day: Day) -> Vec<PartExample> {
+    fs::read_to_string(get_puzzle_path(day))
+        .map(|markdown| extract(&markdown))
+        .unwrap_or_default()
+}
+
+/// Formats an expected answer as the value returned by a part, e.g. `Some(42)`.
+pub fn answer_expr(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("Some({answer:?})"),
+        None => "None".to_string(),
+    }
+}
+
+/// Writes the examples of the downloaded puzzle description to `data/<year>/examples` and fills
+/// the expected answers into the tests of the solution. Example files with content and tests that
+/// already expect a value are left untouched, so this can run again when part two unlocks.
+pub fn update(day: Day) {
+    let parts = from_puzzle(day);
+    let year = Year::selected();
+    let examples_dir = year.data_dir().join("examples");
+
+    for (i, part) in parts.iter().enumerate() {
+        let Some(input) = &part.input else {
+            continue;
+        };
+
+        let path = examples_dir.join(example_file_name(day, i + 1));
+        if has_content(&path) {
+            continue;
+        }
+
+        match fs::create_dir_all(&examples_dir).and_then(|()| fs::write(&path, input)) {
+            Ok(()) => println!(
+                "🎄 Wrote example of part {} to \"{}\".",
+                i + 1,
+                path.display()
+            ),
+            Err(e) => eprintln!("Failed to write example file: {e}"),
+        }
+    }
+
+    let module_path = year.bin_path(day);
+    let Ok(source) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let answers = [0, 1].map(|i| parts.get(i).and_then(|p| p.answer.as_deref()));
+    let second_example = has_content(&examples_dir.join(example_file_name(day, 2)));
+    let patched = patch_tests(&source, answers, second_example);
+
+    if patched != source {
+        match fs::write(&module_path, patched) {
+            Ok(()) => println!("🎄 Filled example answers into \"{module_path}\"."),
+            Err(e) => eprintln!("Failed to update module file: {e}"),
+        }
+    }
+}
+
+/// The first example is stored as `NN.txt`, further ones follow the `NN-2.txt` convention of `read_file_part`.
+fn example_file_name(day: Day, part: usize) -> String {
+    if part == 1 {
+        format!("{day}.txt")
+    } else {
+        format!("{day}-{part}.txt")
+    }
+}
+
+fn has_content(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| !content.trim().is_empty())
+}
+
+/// Replaces `None` in the assertions of the generated tests with the expected answers.
+/// With a second example, `test_part_two` is pointed to `NN-2.txt`.
+fn patch_tests(source: &str, answers: [Option<&str>; 2], second_example: bool) -> String {
+    let mut source = source.to_string();
+
+    for (i, name) in ["fn test_part_one()", "fn test_part_two()"]
+        .iter()
+        .enumerate()
+    {
+        let Some(start) = source.find(name) else {
+            continue;
+        };
+        let end = source[start..]
+            .find("\n    }")
+            .map_or(source.len(), |len| start + len);
+
+        let mut body = source[start..end].to_string();
+
+        if let Some(answer) = answers[i] {
+            body = body.replace(
+                "assert_eq!(result, None);",
+                &format!("assert_eq!(result, {});", answer_expr(Some(answer))),
+            );
+        }
+
+        if i == 1 && second_example {
+            body = body.replace(
+                "read_year_file(YEAR, \"examples\", DAY)",
+                "read_year_file_part(YEAR, \"examples\", DAY, 2)",
+            );
+        }
+
+        source.replace_range(start..end, &body);
+    }
+
+    source
+}
+
+/* -------------------------------------------------------------------------- */
+
+enum Block<'a> {
+    Text(Vec<&'a str>),
+    Code(String),
+}
+
+/// Splits a puzzle description at the heading of part two.
+pub fn split_parts(markdown: &str) -> Vec<&str> {
+    match markdown.find("--- Part Two ---") {
+        Some(index) => {
+            let start = markdown[..index].rfind('\n').map_or(0, |i| i + 1);
+            vec![&markdown[..start], &markdown[start..]]
+        }
+        None => vec![markdown],
+    }
+}
+
+/// Splits markdown into fenced code blocks and the text between them.
+fn blocks(markdown: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut text = vec![];
+    let mut code: Option<String> = None;
+
+    for line in markdown.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match (&mut code, is_fence) {
+            (Some(content), true) => {
+                blocks.push(Block::Code(std::mem::take(content)));
+                code = None;
+            }
+            (Some(content), false) => {
+                content.push_str(line);
+                content.push('\n');
+            }
+            (None, true) => {
+                blocks.push(Block::Text(std::mem::take(&mut text)));
+                code = Some(String::new());
+            }
+            (None, false) => text.push(line),
+        }
+    }
+
+    blocks.push(Block::Text(text));
+    blocks
+}
+
+/// The first code block introduced by a paragraph mentioning an example.
+/// Falls back to the first code block if `fallback` is set.
+fn example_input(blocks: &[Block], fallback: bool) -> Option<String> {
+    let introduced = blocks.windows(2).find_map(|pair| match pair {
+        [Block::Text(lines), Block::Code(code)] => {
+            let paragraph = lines
+                .iter()
+                .rev()
+                .skip_while(|line| line.trim().is_empty())
+                .take_while(|line| !line.trim().is_empty())
+                .map(|line| line.to_lowercase())
+                .collect::<Vec<_>>();
+
+            paragraph
+                .iter()
+                .any(|line| line.contains("example"))
+                .then(|| code.clone())
+        }
+        _ => None,
+    });
+
+    introduced.or_else(|| {
+        fallback
+            .then(|| {
+                blocks.iter().find_map(|block| match block {
+                    Block::Code(code) => Some(code.clone()),
+                    Block::Text(_) => None,
+                })
+            })
+            .flatten()
+    })
+}
+
+/// The last highlighted code span outside of code blocks, written as ``*`42`*`` or `` `*42*` ``.
+fn last_highlight(blocks: &[Block]) -> Option<String> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Text(lines) => Some(lines.join("\n")),
+            Block::Code(_) => None,
+        })
+        .flat_map(|text| {
+            // every odd segment is the content of a code span.
+            let segments: Vec<String> = text.split('`').map(ToString::to_string).collect();
+            (1..segments.len().saturating_sub(1))
+                .step_by(2)
+                .filter_map(|i| {
+                    let content = &segments[i];
+                    let emphasized_around =
+                        segments[i - 1].ends_with('*') && segments[i + 1].starts_with('*');
+                    let emphasized_inside =
+                        content.len() > 2 && content.starts_with('*') && content.ends_with('*');
+
+                    if emphasized_around {
+                        Some(content.trim().to_string())
+                    } else if emphasized_inside {
+                        Some(content.trim_matches('*').trim().to_string())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .next_back()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartExample, answer_expr, extract, patch_tests};
+
+    const PART_ONE: &str = "## --- Day 1: Test ---
+
+The elves need a *sum*. For example:
+
+```
+1
+2
+```
+
+Consider the following illustration:
+
+```
+###
+```
+
+In this example, the sum is *`3`*, the product is `2`.";
+
+    const PART_TWO: &str = "## --- Part Two ---
+
+Now, multiply. Here is another example:
+
+```
+3
+4
+```
+
+Here, the product is `*12*`.";
+
+    #[test]
+    fn extracts_part_one() {
+        assert_eq!(
+            extract(PART_ONE),
+            vec![PartExample {
+                input: Some("1\n2\n".into()),
+                answer: Some("3".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_part_two() {
+        let parts = extract(&format!("{PART_ONE}\n\n{PART_TWO}\n"));
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].input.as_deref(), Some("1\n2\n"));
+        assert_eq!(
+            parts[1],
+            PartExample {
+                input: Some("3\n4\n".into()),
+                answer: Some("12".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn skips_repeated_example() {
+        let part_two =
+            "## --- Part Two ---\n\nUsing the same example:\n\n```\n1\n2\n```\n\nNow it is *`7`*.";
+        let parts = extract(&format!("{PART_ONE}\n\n{part_two}"));
+        assert_eq!(parts[1].input, None);
+        assert_eq!(parts[1].answer.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn falls_back_to_first_block() {
+        let markdown = "## --- Day 2: Test ---\n\nConsider:\n\n```\nabc\n```\n\nThis gives *`1`*.";
+        assert_eq!(extract(markdown)[0].input.as_deref(), Some("abc\n"));
+    }
+
+    #[test]
+    fn formats_answers() {
+        assert_eq!(answer_expr(Some("-12")), "Some(-12)");
+        assert_eq!(answer_expr(Some("a,b")), "Some(\"a,b\")");
+        assert_eq!(answer_expr(None), "None");
+    }
+
+    #[test]
+    fn patches_tests() {
+        let source = crate::template::commands::scaffold::TemplateContext::new(
+            crate::template::Year::new(2025).unwrap(),
+            crate::day!(1),
+        )
+        .render(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/templates/default.txt"
+        )));
+
+        let patched = patch_tests(&source, [Some("3"), None], false);
+        assert!(patched.contains("assert_eq!(result, Some(3));"));
+        assert_eq!(patched.matches("assert_eq!(result, None);").count(), 1);
+
+        let patched = patch_tests(&patched, [Some("4"), Some("12")], true);
+        assert!(patched.contains("assert_eq!(result, Some(3));"));
+        assert!(patched.contains("assert_eq!(result, Some(12));"));
+        assert!(patched.contains(
+            "part_two(&advent_of_code::template::read_year_file_part(YEAR, \"examples\", DAY, 2));"
+        ));
+        assert!(patched.contains(
+            "part_one(&advent_of_code::template::read_year_file(YEAR, \"examples\", DAY));"
+        ));
+    }
+}
@@ -0,0 +1,375 @@
+/// Reads heap profiles written by DHAT (`dhat-heap.json`) and attributes their allocations to the solution source.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+/// Default location of the profile written by `cargo solve <day> --dhat`.
+pub const DEFAULT_FILE: &str = "dhat-heap.json";
+
+/// A stack frame of an allocation, e.g. `_2025_08::part_one (src/bin/2025-08.rs:152:10)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Frame {
+    pub function: String,
+    pub file: String,
+    pub line: u32,
+}
+
+impl Frame {
+    /// Parses a frame of the `ftbl` of a profile, formatted as `<ip>: <function> (<file>:<line>:<column>)`.
+    fn parse(s: &str) -> Option<Self> {
+        let (_, rest) = s.split_once(": ")?;
+        let (function, location) = rest.rsplit_once(" (")?;
+        let mut location = location.strip_suffix(')')?.rsplitn(3, ':');
+        let _column = location.next()?;
+        let line = location.next()?.parse().ok()?;
+        let file = location.next()?;
+
+        Some(Self {
+            function: function.to_string(),
+            file: file.to_string(),
+            line,
+        })
+    }
+
+    /// `file:line` of the frame.
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line)
+    }
+
+    /// Reads the line of source code of this frame.
+    pub fn source_line(&self) -> Option<String> {
+        let content = fs::read_to_string(&self.file).ok()?;
+        let line = content.lines().nth(self.line.checked_sub(1)? as usize)?;
+        Some(line.trim().to_string())
+    }
+}
+
+/// Allocations attributed to a single frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Site {
+    /// Innermost frame in the solution source. Falls back to the innermost frame if no frame is part of the solution.
+    pub frame: Option<Frame>,
+    /// Bytes allocated over the entire run.
+    pub bytes: u64,
+    /// Number of allocations over the entire run.
+    pub blocks: u64,
+    /// Bytes that were live at the global peak of the run.
+    pub peak: u64,
+}
+
+impl Site {
+    fn add(&mut self, other: &Site) {
+        self.bytes += other.bytes;
+        self.blocks += other.blocks;
+        self.peak += other.peak;
+    }
+}
+
+/// Sort order of a profile summary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    Bytes,
+    Peak,
+    Count,
+}
+
+impl SortBy {
+    fn key(self, site: &Site) -> u64 {
+        match self {
+            SortBy::Bytes => site.bytes,
+            SortBy::Peak => site.peak,
+            SortBy::Count => site.blocks,
+        }
+    }
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bytes" => Ok(SortBy::Bytes),
+            "peak" => Ok(SortBy::Peak),
+            "count" => Ok(SortBy::Count),
+            _ => Err(format!("unknown sort order `{s}`, expected bytes, peak or count.")),
+        }
+    }
+}
+
+/// A heap profile, with one site per allocation point of the profile.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub sites: Vec<Site>,
+}
+
+impl Profile {
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("could not read \"{}\": {e}", path.display()))?;
+        let json = JsonValue::from_str(&content)
+            .map_err(|_| format!("\"{}\" is not a valid JSON file.", path.display()))?;
+        Self::from_json(&json, |path| path.is_file())
+    }
+
+    /// Parses a profile. `is_source` decides whether a relative path is a file of the solution source.
+    fn from_json(json: &JsonValue, is_source: impl Fn(&Path) -> bool) -> Result<Self, String> {
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected profile to be a JSON object.")?;
+
+        let frames: Vec<Option<Frame>> = json
+            .get("ftbl")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected profile.ftbl to be an array.")?
+            .iter()
+            .map(|v| v.get::<String>().and_then(|s| Frame::parse(s)))
+            .collect();
+
+        let sites = json
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected profile.pps to be an array.")?
+            .iter()
+            .map(|pp| parse_site(pp, &frames, &is_source))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { sites })
+    }
+
+    /// Sum of all sites.
+    pub fn total(&self) -> Site {
+        let mut total = Site {
+            frame: None,
+            bytes: 0,
+            blocks: 0,
+            peak: 0,
+        };
+        self.sites.iter().for_each(|site| total.add(site));
+        total
+    }
+
+    /// Sites merged by source line, sorted in descending order.
+    pub fn by_location(&self, sort: SortBy) -> Vec<Site> {
+        let mut sites = merge(&self.sites, |frame| frame.clone());
+        sites.sort_by_key(|site| std::cmp::Reverse(sort.key(site)));
+        sites
+    }
+
+    /// Sites merged by function. Used for diffs, as line numbers change when code is edited.
+    pub fn by_function(&self) -> Vec<Site> {
+        merge(&self.sites, |frame| Frame {
+            line: 0,
+            ..frame.clone()
+        })
+    }
+}
+
+/// Change of a function's allocations between two profiles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiteDiff {
+    pub frame: Option<Frame>,
+    pub old: Option<Site>,
+    pub new: Option<Site>,
+}
+
+impl SiteDiff {
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn bytes_delta(&self) -> i64 {
+        self.new.as_ref().map_or(0, |s| s.bytes as i64)
+            - self.old.as_ref().map_or(0, |s| s.bytes as i64)
+    }
+}
+
+/// Compares the allocations of every function, ordered by the largest change of allocated bytes.
+/// Functions that allocate the same in both profiles are left out.
+pub fn diff(old: &Profile, new: &Profile) -> Vec<SiteDiff> {
+    let old_sites = old.by_function();
+    let new_sites = new.by_function();
+
+    let mut diffs: Vec<SiteDiff> = new_sites
+        .iter()
+        .map(|site| SiteDiff {
+            frame: site.frame.clone(),
+            old: old_sites.iter().find(|s| s.frame == site.frame).cloned(),
+            new: Some(site.clone()),
+        })
+        .collect();
+
+    diffs.extend(
+        old_sites
+            .iter()
+            .filter(|site| !new_sites.iter().any(|s| s.frame == site.frame))
+            .map(|site| SiteDiff {
+                frame: site.frame.clone(),
+                old: Some(site.clone()),
+                new: None,
+            }),
+    );
+
+    diffs.retain(|d| {
+        let counts = |s: &Option<Site>| s.as_ref().map(|s| (s.bytes, s.blocks, s.peak));
+        counts(&d.old) != counts(&d.new)
+    });
+    diffs.sort_by_key(|d| std::cmp::Reverse(d.bytes_delta().unsigned_abs()));
+    diffs
+}
+
+fn merge(sites: &[Site], key: impl Fn(&Frame) -> Frame) -> Vec<Site> {
+    let mut merged: Vec<Site> = vec![];
+
+    for site in sites {
+        let frame = site.frame.as_ref().map(&key);
+        match merged.iter_mut().find(|s| s.frame == frame) {
+            Some(existing) => existing.add(site),
+            None => merged.push(Site {
+                frame,
+                ..site.clone()
+            }),
+        }
+    }
+
+    merged
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_site(
+    pp: &JsonValue,
+    frames: &[Option<Frame>],
+    is_source: &impl Fn(&Path) -> bool,
+) -> Result<Site, String> {
+    let json = pp
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected profile.pps to contain objects.")?;
+
+    let number = |key: &str| -> Result<u64, String> {
+        json.get(key)
+            .map_or(Some(0_f64), |v| v.get::<f64>().copied())
+            .map(|v| v as u64)
+            .ok_or(format!("Expected pp.{key} to be a number."))
+    };
+
+    let stack: Vec<&Frame> = json
+        .get("fs")
+        .and_then(|v| v.get::<Vec<JsonValue>>())
+        .ok_or("Expected pp.fs to be an array.")?
+        .iter()
+        .filter_map(|v| v.get::<f64>())
+        .filter_map(|index| frames.get(*index as usize)?.as_ref())
+        .collect();
+
+    // frames are ordered from the allocation outwards, the first frame in the solution source is the most relevant.
+    let frame = stack
+        .iter()
+        .find_map(|frame| resolve(frame, is_source))
+        .or_else(|| stack.first().map(|frame| (*frame).clone()));
+
+    Ok(Site {
+        frame,
+        bytes: number("tb")?,
+        blocks: number("tbk")?,
+        peak: number("gb")?,
+    })
+}
+
+/// DHAT only keeps the last three components of a path, e.g. `crate/src/lib.rs`.
+/// Finds the shortest suffix of the path that is a source file of this repository, excluding the template.
+fn resolve(frame: &Frame, is_source: &impl Fn(&Path) -> bool) -> Option<Frame> {
+    let components: Vec<_> = Path::new(&frame.file).components().collect();
+
+    (0..components.len())
+        .map(|i| components[i..].iter().collect::<PathBuf>())
+        .filter(|path| path.starts_with("src") && !path.starts_with("src/template"))
+        .find(|path| is_source(path))
+        .map(|path| Frame {
+            file: path.to_string_lossy().replace('\\', "/"),
+            ..frame.clone()
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{Frame, Profile, SortBy, diff};
+
+    fn profile(pps: &str) -> Profile {
+        let json = format!(
+            r#"{{
+                "dhatFileVersion": 2,
+                "pps": [{pps}],
+                "ftbl": [
+                    "[root]",
+                    "0x1: alloc::raw_vec::finish_grow (alloc/src/raw_vec.rs:10:5)",
+                    "0x2: _2025_08::part_one (src/bin/2025-08.rs:152:10)",
+                    "0x3: _2025_08::part_one (src/bin/2025-08.rs:160:20)",
+                    "0x4: advent_of_code::template::runner::run_timed (src/template/runner.rs:240:9)",
+                    "0x5: _2025_08::parse (src/bin/2025-08.rs:40:5)"
+                ]
+            }}"#
+        );
+        let json = JsonValue::from_str(&json).unwrap();
+        Profile::from_json(&json, |path| {
+            path == Path::new("src/bin/2025-08.rs") || path == Path::new("src/template/runner.rs")
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_frames() {
+        assert_eq!(
+            Frame::parse("0x10d9e: <T as alloc::Foo>::bar (src/bin/2025-01.rs:12:5)"),
+            Some(Frame {
+                function: "<T as alloc::Foo>::bar".into(),
+                file: "src/bin/2025-01.rs".into(),
+                line: 12,
+            })
+        );
+        assert_eq!(Frame::parse("[root]"), None);
+    }
+
+    #[test]
+    fn attributes_sites_to_solution_source() {
+        let profile = profile(
+            r#"{ "tb": 100, "tbk": 2, "gb": 50, "fs": [1, 2, 4] },
+               { "tb": 300, "tbk": 1, "gb": 0, "fs": [1, 3, 4] },
+               { "tb": 60, "tbk": 3, "gb": 10, "fs": [1, 2, 4] }"#,
+        );
+
+        let sites = profile.by_location(SortBy::Bytes);
+        assert_eq!(sites.len(), 2);
+        assert_eq!(sites[0].frame.as_ref().unwrap().location(), "src/bin/2025-08.rs:160");
+        assert_eq!((sites[1].bytes, sites[1].blocks, sites[1].peak), (160, 5, 60));
+
+        let sites = profile.by_location(SortBy::Count);
+        assert_eq!(sites[0].frame.as_ref().unwrap().line, 152);
+
+        let total = profile.total();
+        assert_eq!((total.bytes, total.blocks, total.peak), (460, 6, 60));
+    }
+
+    #[test]
+    fn diffs_profiles_by_function() {
+        let old = profile(
+            r#"{ "tb": 100, "tbk": 2, "gb": 50, "fs": [1, 2, 4] },
+               { "tb": 300, "tbk": 1, "gb": 0, "fs": [1, 3, 4] },
+               { "tb": 8, "tbk": 1, "gb": 8, "fs": [1, 5, 4] }"#,
+        );
+        let new = profile(r#"{ "tb": 8, "tbk": 1, "gb": 8, "fs": [1, 5, 4] }"#);
+
+        let diffs = diff(&old, &new);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].frame.as_ref().unwrap().function, "_2025_08::part_one");
+        assert_eq!(diffs[0].old.as_ref().unwrap().bytes, 400);
+        assert_eq!(diffs[0].new, None);
+        assert_eq!(diffs[0].bytes_delta(), -400);
+    }
+}
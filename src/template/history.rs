@@ -0,0 +1,260 @@
+/// Append-only history of stored benchmark runs.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// Represents the timings of a single day at the time they were stored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub day: Day,
+    /// Seconds since the unix epoch. `None` for entries migrated from a single snapshot.
+    pub timestamp: Option<u64>,
+    /// Hash of the checked-out commit, if the repository is a git repository.
+    pub commit: Option<String>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+    /// Time spent in the shared `parse` function, if the day has one.
+    pub parse_nanos: Option<f64>,
+}
+
+impl HistoryEntry {
+    pub fn total_nanos(&self) -> f64 {
+        self.parse_nanos.unwrap_or(0_f64)
+            + self.part_1_nanos.unwrap_or(0_f64)
+            + self.part_2_nanos.unwrap_or(0_f64)
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> Option<u64> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+/// Reads the hash of the checked-out commit from `.git` without calling the git binary.
+pub fn head_commit() -> Option<String> {
+    commit_of(Path::new(".git"))
+}
+
+fn commit_of(dot_git: &Path) -> Option<String> {
+    let git_dir = find_git_dir(dot_git)?;
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        // detached HEAD contains the hash itself.
+        return Some(head.to_string());
+    };
+
+    // branches of a worktree are stored in the git directory of the main checkout.
+    let common_dir = find_common_dir(&git_dir);
+
+    for dir in [&git_dir, &common_dir] {
+        if let Ok(hash) = fs::read_to_string(dir.join(reference)) {
+            return Some(hash.trim().to_string());
+        }
+    }
+
+    // refs might have been packed by `git gc`.
+    fs::read_to_string(common_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .filter(|l| !l.starts_with('#') && !l.starts_with('^'))
+        .find_map(|l| {
+            let (hash, name) = l.split_once(' ')?;
+            (name == reference).then(|| hash.to_string())
+        })
+}
+
+/// `.git` is either the git directory or, for worktrees and submodules, a file pointing to it.
+fn find_git_dir(path: &Path) -> Option<PathBuf> {
+    if path.is_dir() {
+        return Some(path.to_path_buf());
+    }
+
+    let content = fs::read_to_string(path).ok()?;
+    let git_dir = content.trim().strip_prefix("gitdir: ")?;
+    // relative paths are relative to the directory containing the `.git` file.
+    Some(path.parent().unwrap_or(Path::new("")).join(git_dir))
+}
+
+/// The git directory of a worktree lists the shared git directory in its `commondir` file.
+fn find_common_dir(git_dir: &Path) -> PathBuf {
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/// Converts days since the unix epoch to a (year, month, day) date.
+/// See: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Parses a duration as printed by the runner (e.g. `74.1ns`, `1.2µs`, `3.4ms`, `1.0s`) into nanoseconds.
+pub fn parse_duration_str(s: &str) -> Option<f64> {
+    // for possible time formats, see:
+    // https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.split(postfix).next()?.trim().parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse("ns"),
+        s if s.contains("µs") => parse("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse("ms").map(|x| x * 1_000_000_f64),
+        _ => parse("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional_number = |v: Option<f64>| v.map_or(JsonValue::Null, JsonValue::Number);
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "timestamp".into(),
+            optional_number(value.timestamp.map(|t| t as f64)),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("part_1_nanos".into(), optional_number(value.part_1_nanos));
+        map.insert("part_2_nanos".into(), optional_number(value.part_2_nanos));
+        map.insert("parse_nanos".into(), optional_number(value.parse_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history.day to be a Day struct.")?;
+
+        let optional_number = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+                .ok_or(format!("Expected history.{key} to be null or number."))
+                .map(|v| v.copied())
+        };
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.commit to be null or string.")?;
+
+        Ok(HistoryEntry {
+            day,
+            timestamp: optional_number("timestamp")?.map(|t| t as u64),
+            commit: commit.cloned(),
+            part_1_nanos: optional_number("part_1_nanos")?,
+            part_2_nanos: optional_number("part_2_nanos")?,
+            // entries stored before parse timings were introduced have no such key.
+            parse_nanos: json
+                .contains_key("parse_nanos")
+                .then(|| optional_number("parse_nanos"))
+                .transpose()?
+                .flatten(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{commit_of, format_timestamp, parse_duration_str};
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_011_200), "2024-12-01 00:00");
+        assert_eq!(format_timestamp(951_827_696), "2000-02-29 12:34");
+    }
+
+    #[test]
+    fn parses_duration_strings() {
+        assert_eq!(parse_duration_str("74.13ns"), Some(74.13));
+        assert_eq!(parse_duration_str("114.4µs"), Some(114_400.0));
+        assert_eq!(parse_duration_str("4.2ms"), Some(4_200_000.0));
+        assert_eq!(parse_duration_str("2s"), Some(2_000_000_000.0));
+        assert_eq!(parse_duration_str("-"), None);
+    }
+
+    #[test]
+    fn reads_commit_of_worktree() {
+        let root = env::temp_dir().join(format!("aoc-history-git-{}", std::process::id()));
+        let main_git = root.join("main/.git");
+        let worktree_git = main_git.join("worktrees/feature");
+        fs::create_dir_all(main_git.join("refs/heads")).unwrap();
+        fs::create_dir_all(&worktree_git).unwrap();
+        fs::create_dir_all(root.join("feature")).unwrap();
+
+        fs::write(main_git.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(main_git.join("refs/heads/main"), "aaa\n").unwrap();
+        fs::write(main_git.join("packed-refs"), "# pack-refs\nbbb refs/heads/feature\n").unwrap();
+        fs::write(worktree_git.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+        fs::write(worktree_git.join("commondir"), "../..\n").unwrap();
+        fs::write(
+            root.join("feature/.git"),
+            "gitdir: ../main/.git/worktrees/feature\n",
+        )
+        .unwrap();
+
+        assert_eq!(commit_of(&main_git), Some("aaa".into()));
+        assert_eq!(commit_of(&root.join("feature/.git")), Some("bbb".into()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,329 @@
+//! Minimal HTML handling for the pages served by adventofcode.com.
+//! This is not a general purpose parser, it only understands the small subset of tags used by puzzle pages.
+
+/// Returns the inner HTML of every `<article>` element, in document order.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        result.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len + "</article>".len()..];
+    }
+
+    result
+}
+
+/// Returns the text of the first `<code>` element following every occurrence of `marker`.
+/// Used to find answers, e.g. "Your puzzle answer was <code>42</code>".
+pub fn code_after<'a>(html: &'a str, marker: &str) -> Vec<&'a str> {
+    html.match_indices(marker)
+        .filter_map(|(index, _)| {
+            let rest = &html[index + marker.len()..];
+            let start = rest.find("<code>")? + "<code>".len();
+            let len = rest[start..].find("</code>")?;
+            Some(&rest[start..start + len])
+        })
+        .collect()
+}
+
+/// Strips all tags and decodes entities, collapsing whitespace.
+pub fn to_text(html: &str) -> String {
+    tokens(html)
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(decode_entities(text)),
+            Token::Tag(_) => None,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Converts the content of a puzzle article to markdown.
+/// Highlighted code like `<code><em>42</em></code>` becomes ``*`42`*``, which marks answers in puzzle descriptions.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut em_in_code = false;
+    let mut hrefs: Vec<Option<String>> = vec![];
+
+    for token in tokens(html) {
+        match token {
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                if in_pre {
+                    out.push_str(&text);
+                } else {
+                    push_collapsed(&mut out, &text);
+                }
+            }
+            Token::Tag(tag) => {
+                let (name, closing) = tag_name(tag);
+                match (name.as_str(), closing) {
+                    ("h2", false) => {
+                        start_block(&mut out);
+                        out.push_str("## ");
+                    }
+                    ("h2" | "p", true) => end_block(&mut out),
+                    ("p", false) => start_block(&mut out),
+                    ("pre", false) => {
+                        start_block(&mut out);
+                        out.push_str("```\n");
+                        in_pre = true;
+                    }
+                    ("pre", true) => {
+                        if !out.ends_with('\n') {
+                            out.push('\n');
+                        }
+                        out.push_str("```");
+                        end_block(&mut out);
+                        in_pre = false;
+                    }
+                    ("code", false) if !in_pre => {
+                        out.push('`');
+                        in_code = true;
+                    }
+                    ("code", true) if !in_pre => {
+                        out.push('`');
+                        if em_in_code {
+                            out.push('*');
+                        }
+                        in_code = false;
+                        em_in_code = false;
+                    }
+                    // move the emphasis out of the code span, markdown does not render it inside.
+                    ("em", false) if in_code && !in_pre && out.ends_with('`') => {
+                        out.pop();
+                        out.push_str("*`");
+                        em_in_code = true;
+                    }
+                    ("em", _) if !in_pre && !in_code => out.push('*'),
+                    ("li", false) => {
+                        if !out.is_empty() && !out.ends_with('\n') {
+                            out.push('\n');
+                        }
+                        out.push_str("- ");
+                    }
+                    ("ul", true) => end_block(&mut out),
+                    ("br", _) => out.push('\n'),
+                    ("a", false) => {
+                        let href = attribute(tag, "href");
+                        if href.is_some() {
+                            out.push('[');
+                        }
+                        hrefs.push(href);
+                    }
+                    ("a", true) => {
+                        if let Some(Some(href)) = hrefs.pop() {
+                            out.push_str(&format!("]({href})"));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Replaces the entities that occur on puzzle pages with their characters.
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('&') {
+        out.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let char = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            char.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+enum Token<'a> {
+    /// Everything between `<` and `>`, e.g. `a href="/2025"`.
+    Tag(&'a str),
+    Text(&'a str),
+}
+
+fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = html;
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        if let Some(tag) = rest.strip_prefix('<')
+            && let Some(end) = tag.find('>')
+        {
+            rest = &tag[end + 1..];
+            return Some(Token::Tag(&tag[..end]));
+        }
+
+        // a `<` without a matching `>` is treated as text.
+        let first = rest.chars().next().map_or(0, char::len_utf8);
+        let end = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+        let text = &rest[..end];
+        rest = &rest[end..];
+        Some(Token::Text(text))
+    })
+}
+
+/// Returns the lowercase name of a tag and whether it is a closing tag.
+fn tag_name(tag: &str) -> (String, bool) {
+    let (closing, tag) = match tag.strip_prefix('/') {
+        Some(tag) => (true, tag),
+        None => (false, tag),
+    };
+
+    let name = tag
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    (name, closing)
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!("{name}=\"");
+    let start = tag.find(&pattern)? + pattern.len();
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+fn start_block(out: &mut String) {
+    if !out.is_empty() {
+        end_block(out);
+    }
+}
+
+/// Ensures that the output ends with an empty line.
+fn end_block(out: &mut String) {
+    let trimmed = out.trim_end_matches([' ', '\n']).len();
+    out.truncate(trimmed);
+    out.push_str("\n\n");
+}
+
+/// Appends text outside of `<pre>`, collapsing whitespace like a browser would.
+fn push_collapsed(out: &mut String, text: &str) {
+    for (i, word) in text.split_whitespace().enumerate() {
+        let at_line_start = out.is_empty() || out.ends_with('\n');
+        if (i > 0 || text.starts_with(char::is_whitespace)) && !at_line_start && !out.ends_with(' ')
+        {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+
+    if text.ends_with(char::is_whitespace) && !text.trim().is_empty() && !out.ends_with('\n') {
+        out.push(' ');
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, code_after, decode_entities, to_markdown, to_text};
+
+    #[test]
+    fn finds_articles() {
+        let html = r#"<main><article class="day-desc"><h2>One</h2></article>
+            <p>between</p><article><p>Two</p></article></main>"#;
+        assert_eq!(articles(html), vec!["<h2>One</h2>", "<p>Two</p>"]);
+    }
+
+    #[test]
+    fn finds_answers() {
+        let html = "<p>Your puzzle answer was <code>42</code>.</p><p>Your puzzle answer was <code>abc</code>.</p>";
+        assert_eq!(
+            code_after(html, "Your puzzle answer was"),
+            vec!["42", "abc"]
+        );
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c &gt; d"),
+            "a < b && c > d"
+        );
+        assert_eq!(decode_entities("&quot;x&#39;&#x41;"), "\"x'A");
+        assert_eq!(decode_entities("AT&T; & more"), "AT&T; & more");
+    }
+
+    #[test]
+    fn converts_to_text() {
+        let html = "<p>That's not the right answer; your answer is <em>too low</em>.\n  <a href=\"/2025/day/1\">[Return]</a></p>";
+        assert_eq!(
+            to_text(html),
+            "That's not the right answer; your answer is too low. [Return]"
+        );
+    }
+
+    #[test]
+    fn converts_to_markdown() {
+        let html = "<h2>--- Day 1: Test ---</h2><p>Read the <a href=\"/about\">rules</a>, then find the <em>sum</em> of <code>1 + 2</code>:</p>\n<pre><code>1\n2\n</code></pre>\n<ul><li>One <code><em>3</em></code></li>\n<li>Two</li></ul>\n<p>Done &gt; here.</p>";
+
+        let expected = "## --- Day 1: Test ---\n\n\
+            Read the [rules](/about), then find the *sum* of `1 + 2`:\n\n\
+            ```\n1\n2\n```\n\n\
+            - One *`3`*\n- Two\n\n\
+            Done > here.";
+
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn converts_non_ascii_text() {
+        let html = "<p>π is <em>—</em> irrational</p><p>é &lt; ü</p>";
+        assert_eq!(to_markdown(html), "π is *—* irrational\n\né < ü");
+    }
+}
@@ -1,44 +1,87 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod record;
+pub mod registry;
 pub mod runner;
+pub mod stats;
 
 pub use day::*;
+pub use year::*;
+pub use run_multi::Execution;
 
+mod answers;
+mod backend;
 mod day;
+mod examples;
+mod heap_profile;
+mod history;
+mod html;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
+mod terminal;
 mod timings;
+mod year;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: alloc::CountingAlloc = alloc::CountingAlloc;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the selected year to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_year_file(Year::selected(), folder, day)
+}
+
+/// Helper function that reads a text file of the selected year to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_year_file_part(Year::selected(), folder, day, part)
+}
+
+/// Helper function that reads a text file of a year to a string, e.g. `data/2024/examples/01.txt`.
+#[must_use]
+pub fn read_year_file(year: Year, folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(year.data_dir()).join(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of a year to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_year_file_part(year: Year, folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(year.data_dir())
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
+/// The year is taken from the name of the binary, e.g. `2024-01`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts return either `Option<T>` or `Result<T, E>`, see [`runner::PartOutput`].
+///
+/// With `parse = <fn>`, the input is parsed once by that function and its output is passed to the parts
+/// by reference, e.g. `solution!(8, parse = parse)` with `fn parse(input: &str) -> Result<Vec<Point>, E>`
+/// and `fn part_one(points: &[Point])`. The parse step is timed separately from the parts.
+/// If it returns an error, the error is reported like a failed part and the parts are skipped.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +93,75 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        #[doc(hidden)]
+        pub fn __run(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::record::PartRecord> {
+            use $crate::template::runner::*;
+            vec![$( run_part($func, input, DAY, $part, options), )*]
+        }
+    };
+
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        #[doc(hidden)]
+        pub fn __run(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::record::PartRecord> {
+            use $crate::template::runner::*;
+            let (parsed, record) = run_parse($parse, input, DAY, options);
+            let Some(parsed) = parsed else {
+                return vec![record];
+            };
+            // calling through a closure lets `&Vec<T>` deref to a `&[T]` parameter.
+            vec![record, $( run_part(|parsed| $func(parsed), &parsed, DAY, $part, options), )*]
+        }
+    };
+
+    (@common $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The year of this solution.
+        const YEAR: $crate::template::Year =
+            $crate::template::Year::__from_bin_name(env!("CARGO_BIN_NAME"));
+
+        /// Registers this day with the in-process runner of the main binary.
+        #[doc(hidden)]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            year: YEAR,
+            day: DAY,
+            run: __run,
+        };
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = RunOptions::from_args();
+            let folder = if options.example { "examples" } else { "inputs" };
+            let input = $crate::template::read_year_file(YEAR, folder, DAY);
+            let records = __run(&input, &options);
+            if let Some(part) = options.submit {
+                std::process::exit(submit_result(&records, part));
+            }
+            if records.iter().any(|r| r.error.is_some()) {
+                std::process::exit(1);
+            }
         }
     };
 }
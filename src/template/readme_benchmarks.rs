@@ -2,8 +2,9 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::alloc::{AllocStats, format_bytes};
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -27,7 +28,7 @@ pub struct TablePosition {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Year::selected().bin_path(day)
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -55,23 +56,46 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // the parse and memory columns are only shown if any day has data for them.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    let has_memory = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_alloc.is_some() || t.part_2_alloc.is_some());
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.extend(["Memory 1", "Memory 2"]);
+    }
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", columns.join(" | ")),
+        format!("|{} :---:  |", " :---: |".repeat(columns.len() - 1)),
     ];
 
+    let cell = |value: Option<String>| format!("`{}`", value.unwrap_or_else(|| "-".into()));
+    let memory = |alloc: Option<AllocStats>| {
+        cell(alloc.map(|a| format!("{} ({} allocs)", format_bytes(a.peak), a.allocs)))
+    };
+
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+        if has_parse {
+            cells.push(cell(timing.parse));
+        }
+        cells.extend([cell(timing.part_1), cell(timing.part_2)]);
+        if has_memory {
+            cells.extend([memory(timing.part_1_alloc), memory(timing.part_2_alloc)]);
+        }
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
@@ -100,6 +124,7 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
+    use crate::template::alloc::AllocStats;
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
@@ -110,20 +135,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         }
     }
 
@@ -169,9 +198,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2025-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2025-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +209,32 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let table = super::construct_table("##", timings, 195.0);
+        assert!(table.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(table.contains("| [Day 1](./src/bin/2025-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(table.contains("| [Day 2](./src/bin/2025-02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_alloc = Some(AllocStats {
+            allocs: 12,
+            bytes: 4096,
+            peak: 2048,
+        });
+
+        let table = super::construct_table("##", timings, 190.0);
+        assert!(table.contains("| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |"));
+        assert!(table.contains("| :---: | :---: | :---: | :---: | :---:  |"));
+        assert!(table.contains(
+            "| [Day 1](./src/bin/2025-01.rs) | `10ms` | `20ms` | `2.0 KiB (12 allocs)` | `-` |"
+        ));
+    }
 }
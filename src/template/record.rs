@@ -0,0 +1,410 @@
+/// Machine-readable result records emitted by solution binaries.
+/// When the environment variable `AOC_RESULT_FILE` is set, every executed part appends one JSON line to that file.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day,
+    alloc::{self, AllocStats},
+    stats::{self, Stats},
+};
+
+pub const RESULT_FILE_ENV: &str = "AOC_RESULT_FILE";
+
+/// Part number of the record emitted for the shared `parse` function of a day.
+pub const PARSE_PART: u8 = 0;
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "failed" => Ok(Status::Failed),
+            _ => Err(format!("unknown status `{s}`.")),
+        }
+    }
+}
+
+/// Represents the result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    /// `1` or `2`, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub nanos: f64,
+    pub samples: u128,
+    /// Benchmark statistics, only present if the part was benched.
+    pub stats: Option<Stats>,
+    pub status: Status,
+    /// Error chain of a failed part, joined by `: `.
+    pub error: Option<String>,
+    /// Heap usage of the first run, only present with the `count-allocs` feature.
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartRecord {
+    pub fn new(
+        day: Day,
+        part: u8,
+        answer: Option<String>,
+        duration: &Duration,
+        stats: Option<Stats>,
+    ) -> Self {
+        let status = if answer.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        Self {
+            day,
+            part,
+            answer,
+            nanos: duration.as_nanos() as f64,
+            samples: stats.as_ref().map_or(1, |s| s.samples),
+            stats,
+            status,
+            error: None,
+            alloc: None,
+        }
+    }
+
+    /// Creates the record of the shared parse step of a day.
+    pub fn parse(day: Day, duration: &Duration, stats: Option<Stats>) -> Self {
+        Self {
+            status: Status::Solved,
+            ..Self::new(day, PARSE_PART, None, duration, stats)
+        }
+    }
+
+    pub fn is_parse(&self) -> bool {
+        self.part == PARSE_PART
+    }
+
+    /// Creates the record of a part that returned an error.
+    pub fn failed(
+        day: Day,
+        part: u8,
+        error: String,
+        duration: &Duration,
+        stats: Option<Stats>,
+    ) -> Self {
+        Self {
+            status: Status::Failed,
+            error: Some(error),
+            ..Self::new(day, part, None, duration, stats)
+        }
+    }
+}
+
+thread_local! {
+    /// Records emitted on this thread while [`capture`] runs.
+    static CAPTURED: RefCell<Option<Vec<PartRecord>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns its result along with the records it emitted on this thread.
+/// The records are returned even if `f` panicked, so that the parts which finished before the panic are not lost.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (thread::Result<T>, Vec<PartRecord>) {
+    CAPTURED.set(Some(vec![]));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let records = CAPTURED.take().unwrap_or_default();
+    (result, records)
+}
+
+/// Append a record to the file selected by `AOC_RESULT_FILE`. Does nothing if the variable is not set.
+/// Records are also collected if [`capture`] is running on this thread.
+pub fn emit(record: &PartRecord) -> Result<(), io::Error> {
+    CAPTURED.with_borrow_mut(|captured| {
+        if let Some(captured) = captured {
+            captured.push(record.clone());
+        }
+    });
+
+    let Some(path) = env::var_os(RESULT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = JsonValue::from(record)
+        .stringify()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read all records from a JSON-lines file.
+pub fn read_from_file(path: &Path) -> Result<Vec<PartRecord>, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_lines(&content)
+}
+
+fn parse_lines(content: &str) -> Result<Vec<PartRecord>, String> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("not a valid JSON line."))?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            stats::optional_to_json(value.stats.as_ref()),
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "alloc".into(),
+            alloc::optional_to_json(value.alloc.as_ref()),
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let stats = stats::optional_from_json(json, "stats")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        // records written before errors were recorded have no error field.
+        let error = json
+            .get("error")
+            .map_or(Some(None), |v| {
+                if v.is_null() {
+                    Some(None)
+                } else {
+                    v.get::<String>().map(Some)
+                }
+            })
+            .ok_or("Expected record.error to be null or string.")?;
+
+        let alloc = alloc::optional_from_json(json, "alloc")?;
+
+        Ok(PartRecord {
+            day,
+            part,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            stats,
+            status,
+            error: error.cloned(),
+            alloc,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{PartRecord, Status, capture, emit, parse_lines};
+    use crate::{
+        day,
+        template::{alloc::AllocStats, stats::Stats},
+    };
+
+    #[test]
+    fn roundtrips_answers_with_special_characters() {
+        let records = [
+            PartRecord::new(
+                day!(1),
+                1,
+                Some("@ @ ( ) ms (2s @ 5 samples)".into()),
+                &Duration::from_nanos(74),
+                Stats::from_durations(&[Duration::from_nanos(70), Duration::from_nanos(78)]),
+            ),
+            PartRecord::new(
+                day!(1),
+                2,
+                Some("#..\n.#.\n..#".into()),
+                &Duration::from_millis(2),
+                None,
+            ),
+        ];
+
+        let content = records
+            .iter()
+            .map(|r| JsonValue::from(r).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(content.lines().count(), 2);
+        assert_eq!(parse_lines(&content).unwrap(), records);
+    }
+
+    #[test]
+    fn records_unsolved_parts() {
+        let record = PartRecord::new(day!(3), 2, None, &Duration::from_nanos(10), None);
+        assert_eq!(record.status, Status::Unsolved);
+
+        let content = JsonValue::from(&record).stringify().unwrap();
+        let parsed = parse_lines(&content).unwrap();
+        assert_eq!(parsed[0].answer, None);
+        assert_eq!(parsed[0].status, Status::Unsolved);
+    }
+
+    #[test]
+    fn roundtrips_alloc_stats() {
+        let record = PartRecord {
+            alloc: Some(AllocStats {
+                allocs: 3,
+                bytes: 96,
+                peak: 64,
+            }),
+            ..PartRecord::new(day!(2), 1, Some("1".into()), &Duration::from_nanos(10), None)
+        };
+
+        let content = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(parse_lines(&content).unwrap(), vec![record]);
+    }
+
+    #[test]
+    fn records_failed_parts() {
+        let record = PartRecord::failed(
+            day!(5),
+            1,
+            "invalid line 3: invalid digit found in string".into(),
+            &Duration::from_nanos(10),
+            None,
+        );
+        assert_eq!(record.status, Status::Failed);
+
+        let content = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(parse_lines(&content).unwrap(), vec![record]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        parse_lines(r#"{ "day": "01", "part": 1 }"#).unwrap();
+    }
+
+    #[test]
+    fn captures_records_emitted_before_a_panic() {
+        let (result, records) = capture(|| {
+            emit(&PartRecord::new(day!(1), 1, Some("1".into()), &Duration::ZERO, None)).unwrap();
+            panic!("part two");
+        });
+
+        assert!(result.is_err());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].part, 1);
+
+        // records are only collected while `capture` runs.
+        emit(&PartRecord::new(day!(1), 2, None, &Duration::ZERO, None)).unwrap();
+        let (result, records) = capture(|| 2);
+        assert_eq!((result.unwrap(), records.len()), (2, 0));
+    }
+}
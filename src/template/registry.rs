@@ -0,0 +1,18 @@
+/// Registry of solutions that are linked into the main binary.
+/// Every `src/bin/<year>-<day>.rs` is included as a module of the main binary by `build.rs`. The [`solution!`](crate::solution)
+/// macro exposes each day as a [`Solution`], which allows `all` and `time` to run days without spawning a process per day.
+use crate::template::{Day, Year, record::PartRecord, runner::RunOptions};
+
+/// Entry point of a single day.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Runs all parts of the day against the passed input.
+    pub run: fn(&str, &RunOptions) -> Vec<PartRecord>,
+}
+
+/// Looks up the solution for a day of a year.
+pub fn find(solutions: &[Solution], year: Year, day: Day) -> Option<&Solution> {
+    solutions.iter().find(|s| s.year == year && s.day == day)
+}
@@ -1,51 +1,221 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, Year,
+    record::{self, PARSE_PART, PartRecord, Status},
+    registry::{self, Solution},
+    runner::{BenchConfig, RunOptions},
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Describes how [`run_multi`] invokes solutions.
+#[derive(Clone, Copy, Debug)]
+pub enum Execution<'a> {
+    /// Every day runs as a separate `cargo run --bin <day>` invocation.
+    ChildProcess { is_release: bool },
+    /// Days run inside the current process, using the solutions linked into the main binary.
+    InProcess(&'a [Solution]),
+}
 
-    let mut need_space = false;
+/// Result of [`run_multi`].
+pub struct MultiRun {
+    /// Records of all parts that ran, in day order.
+    pub records: Vec<PartRecord>,
+    /// Timings of the benched days, only present if a [`BenchConfig`] was passed.
+    pub timings: Option<Timings>,
+}
 
+/// Runs the passed days and prints their output in day order. If a [`BenchConfig`] is passed, the days are benched and their timings are returned.
+/// With `jobs > 1`, up to `jobs` child processes run concurrently. Their output is buffered and printed per day.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    execution: Execution,
+    bench: Option<&BenchConfig>,
+    jobs: usize,
+) -> MultiRun {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut all_records: Vec<PartRecord> = vec![];
+
+    let mut report = |index: usize, day: Day, run: &dyn Fn() -> Vec<PartRecord>| {
+        if index > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        let records = run();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if records.is_empty() {
+            println!("Not solved.");
+        } else {
+            timings.push(timing_from_records(&records, day));
+            all_records.extend(records);
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    match execution {
+        Execution::ChildProcess { is_release } if jobs > 1 => {
+            run_parallel(&days, bench, is_release, jobs, |index, day, output| {
+                report(index, day, &|| output.forward());
+            });
+        }
+        _ => {
+            if jobs > 1 {
+                eprintln!(
+                    "Warning: `--jobs` is not supported with `--in-process`, running days sequentially."
+                );
             }
-        });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+            for (index, day) in days.iter().enumerate() {
+                report(index, *day, &|| match execution {
+                    Execution::ChildProcess { is_release } => {
+                        child_commands::run_solution(*day, bench, is_release).unwrap()
+                    }
+                    Execution::InProcess(solutions) => run_in_process(solutions, *day, bench),
+                });
+            }
+        }
+    }
+
+    let timings = bench.map(|_| {
+        let timings = Timings {
+            data: timings,
+            ..Default::default()
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    MultiRun {
+        records: all_records,
+        timings,
     }
 }
 
+/// Runs the days on `jobs` worker threads and passes their captured output to `on_output` in day order.
+fn run_parallel(
+    days: &[Day],
+    bench: Option<&BenchConfig>,
+    is_release: bool,
+    jobs: usize,
+    mut on_output: impl FnMut(usize, Day, child_commands::CapturedOutput),
+) {
+    // build once upfront, so the concurrent `cargo run` invocations do not wait on each other's build lock.
+    if let Err(e) = child_commands::build_solutions(is_release) {
+        eprintln!("Failed to build solutions: {e:?}");
+        return;
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(index) else {
+                        break;
+                    };
+
+                    let output = child_commands::run_solution_captured(*day, bench, is_release)
+                        .unwrap_or_else(|e| child_commands::CapturedOutput::from_error(&e));
+
+                    if sender.send((index, output)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        // outputs arrive in completion order, hold them back until all previous days were printed.
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, output) in receiver {
+            pending.insert(index, output);
+
+            while let Some(output) = pending.remove(&next_to_print) {
+                on_output(next_to_print, days[next_to_print], output);
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+/// Run a day through the registry of linked solutions.
+/// A panicking solution does not abort the other days. It keeps the records of the parts that finished
+/// and gets a failed record carrying the panic message for the part that panicked.
+fn run_in_process(
+    solutions: &[Solution],
+    day: Day,
+    bench: Option<&BenchConfig>,
+) -> Vec<PartRecord> {
+    let Some(solution) = registry::find(solutions, Year::selected(), day) else {
+        return vec![];
+    };
+
+    let options = RunOptions {
+        bench: bench.copied(),
+        ..RunOptions::default()
+    };
+
+    let (result, mut records) = record::capture(|| {
+        let input = super::read_file("inputs", day);
+        (solution.run)(&input, &options)
+    });
+
+    let payload = match result {
+        Ok(records) => return records,
+        Err(payload) => payload,
+    };
+
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into());
+
+    // the panicking part is the one after the last part that emitted a record.
+    let part = records.last().map_or(1, |r| r.part + 1).max(1);
+    println!("Part {part}: {ANSI_BOLD}⚠ panicked{ANSI_RESET}");
+    println!("    {message}");
+
+    records.push(PartRecord::failed(
+        day,
+        part,
+        format!("panicked: {message}"),
+        &Duration::ZERO,
+        None,
+    ));
+    records
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -61,199 +231,308 @@ impl From<std::io::Error> for Error {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Year::selected().bin_path(day)
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the result records they emit.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{
+        Day, Year, alloc,
+        record::{self, PartRecord, RESULT_FILE_ENV},
+        runner::BenchConfig,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        env, fs,
+        path::{Path, PathBuf},
+        process::{self, Command},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day and collect the result records it wrote.
+    pub fn run_solution(
+        day: Day,
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+    ) -> Result<Vec<PartRecord>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let record_path = get_record_path(day);
+        let _ = fs::remove_file(&record_path);
+
+        // stdout/stderr are forwarded as-is, results are read from the record file afterwards.
+        build_command(day, bench, is_release)
+            .env(RESULT_FILE_ENV, &record_path)
+            .status()?;
+
+        Ok(take_records(&record_path))
+    }
+
+    /// Output of a solution bin that ran with piped stdout / stderr.
+    pub struct CapturedOutput {
+        stdout: Vec<u8>,
+        stderr: Vec<u8>,
+        records: Vec<PartRecord>,
+    }
+
+    impl CapturedOutput {
+        pub fn from_error(e: &Error) -> Self {
+            Self {
+                stdout: vec![],
+                stderr: format!("Failed to run solution: {e:?}\n").into_bytes(),
+                records: vec![],
+            }
+        }
+
+        /// Forwards the captured output to stdout / stderr and returns the records.
+        pub fn forward(&self) -> Vec<PartRecord> {
+            print!("{}", String::from_utf8_lossy(&self.stdout));
+            eprint!("{}", String::from_utf8_lossy(&self.stderr));
+            self.records.clone()
+        }
+    }
+
+    /// Build all solution bins, so that they can be run concurrently afterwards.
+    pub fn build_solutions(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if alloc::ENABLED {
+            args.extend(["--features", "count-allocs"]);
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        Command::new("cargo").args(&args).status()?;
+        Ok(())
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    /// Run the solution bin for a given day while capturing its output instead of forwarding it.
+    pub fn run_solution_captured(
+        day: Day,
+        bench: Option<&BenchConfig>,
+        is_release: bool,
+    ) -> Result<CapturedOutput, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(CapturedOutput {
+                stdout: vec![],
+                stderr: vec![],
+                records: vec![],
+            });
+        }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let record_path = get_record_path(day);
+        let _ = fs::remove_file(&record_path);
 
-        let mut output = vec![];
+        let output = build_command(day, bench, is_release)
+            .env(RESULT_FILE_ENV, &record_path)
+            .output()?;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        Ok(CapturedOutput {
+            stdout: output.stdout,
+            stderr: output.stderr,
+            records: take_records(&record_path),
+        })
+    }
+
+    fn build_command(day: Day, bench: Option<&BenchConfig>, is_release: bool) -> Command {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            Year::selected().bin_name(day),
+        ];
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if is_release {
+            args.push("--release".into());
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        // count allocations of the child, too, if this binary counts them.
+        if alloc::ENABLED {
+            args.push("--features".into());
+            args.push("count-allocs".into());
+        }
 
-        Ok(output)
+        if let Some(bench) = bench {
+            // mirror `--time` flag and bench settings to child invocations.
+            args.push("--".into());
+            args.push("--time".into());
+            args.extend(bench.to_args());
+        }
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+        cmd
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Reads and removes the record file of a run.
+    fn take_records(record_path: &Path) -> Vec<PartRecord> {
+        let records = if record_path.exists() {
+            record::read_from_file(record_path).unwrap_or_else(|e| {
+                eprintln!("Could not read result records: {e}");
+                vec![]
+            })
+        } else {
+            vec![]
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let _ = fs::remove_file(record_path);
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        records
+    }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+    fn get_record_path(day: Day) -> PathBuf {
+        env::temp_dir().join(format!("aoc-results-{}-{day}.jsonl", process::id()))
+    }
+}
+
+fn timing_from_records(records: &[PartRecord], day: Day) -> Timing {
+    let mut timings = Timing {
+        day,
+        ..Default::default()
+    };
+
+    records
+        .iter()
+        .filter(|r| r.day == day && r.status == Status::Solved)
+        .for_each(|r| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let timing_str = format!("{:.1?}", Duration::from_nanos(r.nanos as u64));
+
+            match r.part {
+                PARSE_PART => {
+                    timings.parse = Some(timing_str);
+                    timings.parse_stats.clone_from(&r.stats);
+                    timings.parse_alloc = r.alloc;
+                }
+                1 => {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats.clone_from(&r.stats);
+                    timings.part_1_alloc = r.alloc;
+                }
+                2 => {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats.clone_from(&r.stats);
+                    timings.part_2_alloc = r.alloc;
                 }
+                _ => return,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            timings.total_nanos += r.nanos;
+        });
 
-        timings
-    }
+    timings
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see:
-        // https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        #[allow(clippy::double_ended_iterator_last)]
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::timing_from_records;
+
+    use crate::{
+        day,
+        template::{record::PartRecord, stats::Stats},
+    };
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn collects_execution_times() {
+        let res = timing_from_records(
+            &[
+                PartRecord::new(
+                    day!(1),
+                    1,
+                    Some("0".into()),
+                    &Duration::from_nanos(74),
+                    Stats::from_durations(&[Duration::from_nanos(74)]),
+                ),
+                PartRecord::new(
+                    day!(1),
+                    2,
+                    Some("10".into()),
+                    &Duration::from_nanos(74_130_000),
+                    None,
+                ),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
+        assert_eq!(res.part_1_stats.unwrap().median, 74_f64);
+        assert_eq!(res.part_2_stats, None);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn collects_with_patterns_in_answer() {
+        let res = timing_from_records(
+            &[
+                PartRecord::new(
+                    day!(1),
+                    1,
+                    Some("@ @ @ ( ) ms (2s @ 5 samples)".into()),
+                    &Duration::from_secs(2),
+                    None,
+                ),
+                PartRecord::new(
+                    day!(1),
+                    2,
+                    Some("line 1\nline 2 (1s @ 3 samples)".into()),
+                    &Duration::from_millis(100),
+                    None,
+                ),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 2100000000_f64);
+        assert_eq!(res.part_1.unwrap(), "2.0s");
+        assert_eq!(res.part_2.unwrap(), "100.0ms");
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn collects_missing_parts() {
+        let res = timing_from_records(
+            &[
+                PartRecord::new(day!(1), 1, None, &Duration::from_nanos(10), None),
+                PartRecord::new(day!(1), 2, None, &Duration::from_nanos(10), None),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn collects_parse_time() {
+        let res = timing_from_records(
+            &[
+                PartRecord::parse(day!(1), &Duration::from_micros(20), None),
+                PartRecord::new(day!(1), 1, Some("1".into()), &Duration::from_micros(5), None),
+                PartRecord::new(day!(1), 2, Some("2".into()), &Duration::from_micros(5), None),
+            ],
+            day!(1),
+        );
+        assert_approx_eq!(res.total_nanos, 30000_f64);
+        assert_eq!(res.parse.unwrap(), "20.0µs");
+        assert_eq!(res.part_1.unwrap(), "5.0µs");
     }
 }
@@ -1,63 +1,307 @@
 /// Encapsulates code that interacts with solution functions.
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::Answers;
+use crate::template::backend::Backend;
+use crate::template::examples;
+use crate::template::history;
+use crate::template::record::{self, PARSE_PART, PartRecord};
+use crate::template::stats::Stats;
+use crate::template::submissions::{Submission, SubmitVerdict, Submissions};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Settings for the benchmark mode.
+#[derive(Clone, Copy, Debug)]
+pub struct BenchConfig {
+    /// Minimum time spent running the function before measuring, including the initial run.
+    pub warm_up: Duration,
+    /// Targeted total time of the measurement phase.
+    pub measurement_time: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warm_up: Duration::from_millis(100),
+            measurement_time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Reads overrides for the defaults from `--warm-up <ms>`, `--measurement-time <ms>`, `--min-samples <n>` and `--max-samples <n>`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let defaults = Self::default();
+
+        let value_of = |flag: &str| -> Option<u64> {
+            let index = args.iter().position(|x| x == flag)?;
+            args.get(index + 1)?.parse().ok()
+        };
+
+        Self {
+            warm_up: value_of("--warm-up").map_or(defaults.warm_up, Duration::from_millis),
+            measurement_time: value_of("--measurement-time")
+                .map_or(defaults.measurement_time, Duration::from_millis),
+            min_samples: value_of("--min-samples").map_or(defaults.min_samples, u128::from),
+            max_samples: value_of("--max-samples").map_or(defaults.max_samples, u128::from),
+        }
+    }
+
+    /// Converts the config back into command-line arguments, used to forward it to child invocations.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--warm-up".into(),
+            self.warm_up.as_millis().to_string(),
+            "--measurement-time".into(),
+            self.measurement_time.as_millis().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+}
+
+/// Controls how [`run_part`] executes a part.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part with this config instead of running it once.
+    pub bench: Option<BenchConfig>,
+    /// Submit the result of this part.
+    pub submit: Option<u8>,
+    /// Run against the example input instead of the puzzle input.
+    pub example: bool,
+}
+
+impl RunOptions {
+    /// Reads the options from the arguments passed to a solution binary, i.e. `--time`, `--submit <part>` and `--example`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let bench = args
+            .iter()
+            .any(|x| x == "--time")
+            .then(BenchConfig::from_args);
+
+        let submit = args.iter().position(|x| x == "--submit").map(|index| {
+            let Some(Ok(part)) = args.get(index + 1).map(|x| x.parse::<u8>()) else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        let example = args.iter().any(|x| x == "--example");
+
+        if example && submit.is_some() {
+            eprintln!("Answers for the example input can not be submitted.");
+            process::exit(1);
+        }
+
+        Self {
+            bench,
+            submit,
+            example,
+        }
+    }
+}
+
+/// Result of a single run of a part, see [`PartOutput`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(String),
+    /// The part returned `None`, i.e. it is not implemented yet.
+    Unsolved,
+    /// The part returned an error. Contains the error followed by its sources.
+    Failed(Vec<String>),
+}
+
+/// Return types that a part can have: `Option<T>` and `Result<T, E>`, where `T` implements [`Display`]
+/// and `E` converts into a `Box<dyn Error>`, e.g. any [`Error`], a `String` or a `&str`.
+pub trait PartOutput {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Solved(answer.to_string()),
+            Err(e) => Outcome::Failed(error_chain(e)),
+        }
+    }
+}
+
+/// Formats an error followed by all of its sources.
+fn error_chain(e: impl Into<Box<dyn Error>>) -> Vec<String> {
+    let e: Box<dyn Error> = e.into();
+    std::iter::successors(Some(&*e), |&e| e.source())
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub fn run_part<I: Copy, T: PartOutput>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartRecord {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (outcome, duration, stats, alloc) =
+        run_timed(func, input, options.bench.as_ref(), |result| {
+            let outcome = result.into_outcome();
+            print_result(&outcome, &part_str, "");
+            outcome
+        });
+
+    let samples = stats.as_ref().map_or(1, |s| s.samples);
+    print_result(
+        &outcome,
+        &part_str,
+        &format_duration(&duration, samples, alloc.as_ref()),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some(stats) = &stats {
+        println!("        {ANSI_ITALIC}{}{ANSI_RESET}", stats.summary());
+    }
+
+    let record = PartRecord {
+        alloc,
+        ..match outcome {
+            Outcome::Solved(answer) => PartRecord::new(day, part, Some(answer), &duration, stats),
+            Outcome::Unsolved => PartRecord::new(day, part, None, &duration, stats),
+            Outcome::Failed(chain) => {
+                PartRecord::failed(day, part, chain.join(": "), &duration, stats)
+            }
+        }
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Err(e) = record::emit(&record) {
+        eprintln!("Failed to write result record: {e}");
     }
+
+    record
+}
+
+/// Runs the shared `parse` function of a day, timing it like a part.
+/// Returns the parsed input, which is passed to both parts by reference, or `None` if parsing failed.
+pub fn run_parse<P, E: Into<Box<dyn Error>>>(
+    func: impl Fn(&str) -> Result<P, E>,
+    input: &str,
+    day: Day,
+    options: &RunOptions,
+) -> (Option<P>, PartRecord) {
+    let (parsed, duration, stats, alloc) =
+        run_timed(func, input, options.bench.as_ref(), |parsed| {
+            let parsed = parsed.map_err(error_chain);
+            print!("{}", if parsed.is_ok() { "Parse:" } else { "Parse: ⚠" });
+            parsed
+        });
+
+    let samples = stats.as_ref().map_or(1, |s| s.samples);
+    let duration_str = format_duration(&duration, samples, alloc.as_ref());
+    match &parsed {
+        Ok(_) => {
+            print!("\r");
+            println!("Parse:{duration_str}");
+        }
+        Err(chain) => print_result(&Outcome::Failed(chain.clone()), "Parse", &duration_str),
+    }
+
+    if let Some(stats) = &stats {
+        println!("        {ANSI_ITALIC}{}{ANSI_RESET}", stats.summary());
+    }
+
+    let record = PartRecord {
+        alloc,
+        ..match &parsed {
+            Ok(_) => PartRecord::parse(day, &duration, stats),
+            Err(chain) => PartRecord::failed(day, PARSE_PART, chain.join(": "), &duration, stats),
+        }
+    };
+
+    if let Err(e) = record::emit(&record) {
+        eprintln!("Failed to write result record: {e}");
+    }
+
+    (parsed.ok(), record)
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
+///  2. in release, the function is benched according to the passed [`BenchConfig`].
+///
+/// The result of the first run is passed to `hook`, which is called before benching starts.
+/// Allocations are counted for the first run only, see [`alloc::measure`].
+fn run_timed<I: Copy, T, O>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    bench_config: Option<&BenchConfig>,
+    hook: impl FnOnce(T) -> O,
+) -> (O, Duration, Option<Stats>, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if let Some(config) = bench_config {
+        let stats = bench(func, input, &base_time, config);
+        (result, stats.mean_duration(), Some(stats), alloc)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, alloc)
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // the initial run counts towards the warm-up phase.
+    let mut warm_up_runs: u32 = 1;
+    let warm_up_timer = Instant::now();
+    while *base_time + warm_up_timer.elapsed() < config.warm_up {
+        black_box(func(black_box(input)));
+        warm_up_runs += 1;
+    }
+    let estimate = (*base_time + warm_up_timer.elapsed()) / warm_up_runs;
+
+    let min_samples = cmp::max(config.min_samples, 1);
+    let bench_iterations = (config.measurement_time.as_nanos() / cmp::max(estimate.as_nanos(), 10))
+        .clamp(min_samples, cmp::max(min_samples, config.max_samples));
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,35 +311,25 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_durations(&timers).expect("at least one sample is measured")
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+fn format_duration(duration: &Duration, samples: u128, alloc: Option<&AllocStats>) -> String {
+    let alloc = alloc.map_or_else(String::new, |a| format!(" · {}", a.summary()));
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
-        format!(" ({duration:.1?})")
+        format!(" ({duration:.1?}{alloc})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} @ {samples} samples{alloc})")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match outcome {
+        Outcome::Solved(result) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -114,7 +348,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,44 +356,168 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(chain) => {
+            if is_intermediate_result {
+                print!("{part}: ⚠");
+            } else {
+                print!("\r");
+                println!("{part}: {ANSI_BOLD}⚠ error{ANSI_RESET}{duration_str}");
+                for (i, message) in chain.iter().enumerate() {
+                    if i == 0 {
+                        println!("    {message}");
+                    } else {
+                        println!("    caused by: {message}");
+                    }
+                }
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Exit code of `cargo solve --submit` if the submission ledger refused to send the answer.
+pub const REFUSED_EXIT_CODE: i32 = 7;
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+/// Try to submit the result of one part via the built-in client or aoc-cli.
+/// Answers that the submission ledger already knows to be wrong are not sent.
+/// Returns the exit code for the verdict, see [`SubmitVerdict::exit_code`].
+pub fn submit_result(records: &[PartRecord], part: u8) -> i32 {
+    let Some((day, answer)) = records
+        .iter()
+        .find(|r| r.part == part)
+        .and_then(|r| Some((r.day, r.answer.clone()?)))
+    else {
+        eprintln!("Part {part} has no result to submit.");
+        return 1;
+    };
+
+    let mut submissions = Submissions::read_from_file();
+
+    if let Err(refusal) = submissions.check(day, part, &answer) {
+        eprintln!("Not submitting {answer}: {refusal}");
+        return REFUSED_EXIT_CODE;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let backend = Backend::detect().unwrap_or_else(|e| {
+        eprintln!("{e}");
         process::exit(1);
-    }
+    });
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result via {}...", backend.name());
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let verdict = match backend.submit(day, part, &answer) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            return 1;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    let marker = if verdict == SubmitVerdict::Correct {
+        "★"
+    } else {
+        "✖"
+    };
+    println!("{ANSI_BOLD}{marker} Day {day} Part {part}: {verdict}{ANSI_RESET}");
+
+    let exit_code = verdict.exit_code();
+
+    if verdict == SubmitVerdict::Correct {
+        // `cargo verify` checks later runs against the accepted answer.
+        let mut answers = Answers::read_from_file();
+        answers.set(day, part, &answer);
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store the accepted answer: {e}");
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if part == 1 && verdict == SubmitVerdict::Correct {
+        // part two unlocked, its description usually comes with a new example.
+        match backend.download_puzzle(day) {
+            Ok(()) => examples::update(day),
+            Err(e) => eprintln!("Failed to download part two: {e}"),
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    submissions.push(Submission {
+        day,
+        part,
+        answer,
+        verdict,
+        timestamp: history::now(),
+    });
+
+    if let Err(e) = submissions.store_file() {
+        eprintln!("Failed to record submission: {e}");
+    }
+
+    exit_code
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::error::Error;
+    use std::fmt;
+
+    use super::{Outcome, PartOutput};
+
+    #[derive(Debug)]
+    struct LineError(usize, std::num::ParseIntError);
+
+    impl fmt::Display for LineError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "invalid line {}", self.0)
+        }
+    }
+
+    impl Error for LineError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.1)
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).into_outcome(), Outcome::Solved("42".into()));
+        assert_eq!(None::<u64>.into_outcome(), Outcome::Unsolved);
+    }
+
+    #[test]
+    fn converts_results() {
+        assert_eq!(
+            Ok::<_, String>("abc").into_outcome(),
+            Outcome::Solved("abc".into())
+        );
+        assert_eq!(
+            Err::<u64, _>("x".parse::<u64>().unwrap_err()).into_outcome(),
+            Outcome::Failed(vec!["invalid digit found in string".into()])
+        );
+    }
+
+    #[test]
+    fn prints_error_chain_of_errors() {
+        let source = "x".parse::<u64>().unwrap_err();
+        let result: Result<u64, LineError> = Err(LineError(3, source));
+        assert_eq!(
+            result.into_outcome(),
+            Outcome::Failed(vec![
+                "invalid line 3".into(),
+                "invalid digit found in string".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn prints_error_chain_of_boxed_errors() {
+        let source = "x".parse::<u64>().unwrap_err();
+        let result: Result<u64, Box<dyn Error>> = Err(Box::new(LineError(3, source)));
+        assert_eq!(
+            result.into_outcome(),
+            Outcome::Failed(vec![
+                "invalid line 3".into(),
+                "invalid digit found in string".into()
+            ])
+        );
+    }
 }
@@ -0,0 +1,203 @@
+/// Summary statistics for a series of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics over a set of samples, all durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: u128,
+    pub samples: u128,
+}
+
+impl Stats {
+    /// Computes statistics for the passed samples. Returns `None` if there are no samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+        let (fence_low, fence_high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Self {
+            mean,
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            median: percentile(&nanos, 50.0),
+            p95: percentile(&nanos, 95.0),
+            std_dev: variance.sqrt(),
+            outliers: nanos
+                .iter()
+                .filter(|x| **x < fence_low || **x > fence_high)
+                .count() as u128,
+            samples: nanos.len() as u128,
+        })
+    }
+
+    /// The mean as a [`Duration`].
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn mean_duration(&self) -> Duration {
+        Duration::from_nanos(self.mean as u64)
+    }
+
+    /// One-line human-readable summary, e.g. `min 1.0µs · median 1.1µs · p95 1.4µs · max 3.0µs · σ 120.0ns · 3 outliers`.
+    pub fn summary(&self) -> String {
+        format!(
+            "min {} · median {} · p95 {} · max {} · σ {} · {} outliers",
+            format_nanos(self.min),
+            format_nanos(self.median),
+            format_nanos(self.p95),
+            format_nanos(self.max),
+            format_nanos(self.std_dev),
+            self.outliers
+        )
+    }
+}
+
+/// Formats a nanosecond value the same way `Duration` is debug-printed by the runner.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Percentile over sorted values, linearly interpolated between the closest ranks.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0) * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - lower as f64;
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            mean: number("mean")?,
+            min: number("min")?,
+            max: number("max")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            std_dev: number("std_dev")?,
+            outliers: number("outliers")? as u128,
+            samples: number("samples")? as u128,
+        })
+    }
+}
+
+/// Reads an optional stats object from `key`, treating absent keys and `null` as `None`.
+pub fn optional_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<Stats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => Stats::try_from(v).map(Some),
+    }
+}
+
+/// Converts optional stats into a JSON value, `None` becomes `null`.
+pub fn optional_to_json(stats: Option<&Stats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_basic_statistics() {
+        let stats = Stats::from_durations(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.median, 3.0);
+        assert!((stats.p95 - 4.8).abs() < 1e-9);
+        assert!((stats.std_dev - 2_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.samples, 5);
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats = Stats::from_durations(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, 11.0);
+        assert_eq!(stats.max, 500.0);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_durations(&nanos(&[42])).unwrap();
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.p95, 42.0);
+        assert_eq!(stats.std_dev, 0.0);
+    }
+
+    #[test]
+    fn handles_no_samples() {
+        assert_eq!(Stats::from_durations(&[]), None);
+    }
+}
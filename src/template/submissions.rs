@@ -0,0 +1,501 @@
+/// Local ledger of submitted answers and their verdicts.
+use std::{
+    collections::HashMap, fmt::Display, fs, io::Error, path::PathBuf, str::FromStr, time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+/// The verdict of the Advent of Code server on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitVerdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently. Contains the remaining wait time, if the server stated one.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    /// The response was not recognized. Contains the response text.
+    Unknown(String),
+}
+
+impl SubmitVerdict {
+    /// Classifies the text of a submit response.
+    pub fn from_response(response: &str) -> Self {
+        let lowercase = response.to_lowercase();
+
+        if lowercase.contains("that's the right answer") {
+            Self::Correct
+        } else if lowercase.contains("answer too recently") {
+            Self::RateLimited {
+                wait: parse_wait_time(&lowercase),
+            }
+        } else if lowercase.contains("your answer is too high") {
+            Self::TooHigh
+        } else if lowercase.contains("your answer is too low") {
+            Self::TooLow
+        } else if lowercase.contains("that's not the right answer") {
+            Self::Incorrect
+        } else if lowercase.contains("solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown(response.trim().to_string())
+        }
+    }
+
+    /// Whether the server rejected the answer itself. Rate-limited submissions were never checked.
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Self::Incorrect | Self::TooHigh | Self::TooLow)
+    }
+
+    /// Exit code of `cargo solve --submit` for this verdict.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Correct => 0,
+            Self::Unknown(_) => 1,
+            Self::Incorrect => 2,
+            Self::TooHigh => 3,
+            Self::TooLow => 4,
+            Self::RateLimited { .. } => 5,
+            Self::WrongLevel => 6,
+        }
+    }
+}
+
+impl Display for SubmitVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::TooHigh => write!(f, "incorrect, too high"),
+            Self::TooLow => write!(f, "incorrect, too low"),
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate-limited, {}s left to wait", wait.as_secs())
+            }
+            Self::RateLimited { wait: None } => write!(f, "rate-limited"),
+            Self::WrongLevel => write!(f, "part already solved or not unlocked yet"),
+            Self::Unknown(_) => write!(f, "unrecognized response"),
+        }
+    }
+}
+
+/// Parses the wait time of a rate-limited response, e.g. `You have 1m 5s left to wait.`
+fn parse_wait_time(response: &str) -> Option<Duration> {
+    let end = response.find(" left to wait")?;
+    let start = response[..end].rfind("you have ")? + "you have ".len();
+
+    response[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, factor) = match token.chars().last()? {
+                'h' => (&token[..token.len() - 1], 3600),
+                'm' => (&token[..token.len() - 1], 60),
+                's' => (&token[..token.len() - 1], 1),
+                _ => return None,
+            };
+            value.parse::<u64>().ok().map(|v| v * factor)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Name of a verdict in the JSON file. `None` for verdicts that are not recorded.
+fn verdict_to_str(verdict: &SubmitVerdict) -> Option<&'static str> {
+    match verdict {
+        SubmitVerdict::Correct => Some("correct"),
+        SubmitVerdict::Incorrect => Some("incorrect"),
+        SubmitVerdict::TooHigh => Some("too high"),
+        SubmitVerdict::TooLow => Some("too low"),
+        SubmitVerdict::RateLimited { .. } => Some("rate-limited"),
+        SubmitVerdict::WrongLevel | SubmitVerdict::Unknown(_) => None,
+    }
+}
+
+fn verdict_from_str(s: &str) -> Result<SubmitVerdict, String> {
+    match s {
+        "correct" => Ok(SubmitVerdict::Correct),
+        "incorrect" => Ok(SubmitVerdict::Incorrect),
+        "too high" => Ok(SubmitVerdict::TooHigh),
+        "too low" => Ok(SubmitVerdict::TooLow),
+        "rate-limited" => Ok(SubmitVerdict::RateLimited { wait: None }),
+        _ => Err(format!("unknown verdict `{s}`.")),
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: SubmitVerdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: Option<u64>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    AlreadyRejected { verdict: SubmitVerdict },
+    NotBelow { bound: i128 },
+    NotAbove { bound: i128 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "the part was already solved with {answer}.")
+            }
+            Refusal::AlreadyRejected { verdict } => {
+                write!(f, "the same answer was already rejected ({verdict}).")
+            }
+            Refusal::NotBelow { bound } => {
+                write!(f, "{bound} was already too high, the answer must be lower.")
+            }
+            Refusal::NotAbove { bound } => {
+                write!(f, "{bound} was already too low, the answer must be higher.")
+            }
+        }
+    }
+}
+
+/// All submissions made from this repository, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(file_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(file_path())
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Records a submission. Verdicts that do not judge the answer are not recorded.
+    pub fn push(&mut self, submission: Submission) {
+        if verdict_to_str(&submission.verdict).is_some() {
+            self.data.push(submission);
+        }
+    }
+
+    /// Checks an answer against the previous submissions of the same part before it is sent.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let previous = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(correct) = previous().find(|s| s.verdict == SubmitVerdict::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if let Some(rejected) = previous().find(|s| s.answer == answer && s.verdict.is_rejection())
+        {
+            return Err(Refusal::AlreadyRejected {
+                verdict: rejected.verdict.clone(),
+            });
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric_bound = |verdict: SubmitVerdict| {
+            previous()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(bound) = numeric_bound(SubmitVerdict::TooHigh).min()
+            && value >= bound
+        {
+            return Err(Refusal::NotBelow { bound });
+        }
+
+        if let Some(bound) = numeric_bound(SubmitVerdict::TooLow).max()
+            && value <= bound
+        {
+            return Err(Refusal::NotAbove { bound });
+        }
+
+        Ok(())
+    }
+}
+
+/// Path of the submissions file of the selected year, e.g. `data/2025/submissions.json`.
+fn file_path() -> PathBuf {
+    Year::selected().data_dir().join("submissions.json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_to_str(&value.verdict).unwrap_or_default().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            value
+                .timestamp
+                .map_or(JsonValue::Null, |t| JsonValue::Number(t as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected submission.timestamp to be null or number.")?;
+
+        Ok(Submission {
+            day,
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict: verdict_from_str(verdict)?,
+            timestamp: timestamp.map(|t| *t as u64),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Refusal, Submission, SubmitVerdict, Submissions};
+    use crate::day;
+
+    fn ledger(entries: &[(u8, &str, SubmitVerdict)]) -> Submissions {
+        Submissions {
+            data: entries
+                .iter()
+                .map(|(part, answer, verdict)| Submission {
+                    day: day!(1),
+                    part: *part,
+                    answer: (*answer).to_string(),
+                    verdict: verdict.clone(),
+                    timestamp: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let ledger = ledger(&[
+            (1, "abc", SubmitVerdict::Incorrect),
+            (1, "def", SubmitVerdict::RateLimited { wait: None }),
+        ]);
+
+        assert_eq!(
+            ledger.check(day!(1), 1, "abc"),
+            Err(Refusal::AlreadyRejected {
+                verdict: SubmitVerdict::Incorrect
+            })
+        );
+        assert_eq!(ledger.check(day!(1), 1, "def"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "abc"), Ok(()));
+        assert_eq!(ledger.check(day!(2), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = ledger(&[
+            (1, "100", SubmitVerdict::TooHigh),
+            (1, "80", SubmitVerdict::TooHigh),
+            (1, "10", SubmitVerdict::TooLow),
+            (1, "20", SubmitVerdict::TooLow),
+        ]);
+
+        assert_eq!(
+            ledger.check(day!(1), 1, "90"),
+            Err(Refusal::NotBelow { bound: 80 })
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "15"),
+            Err(Refusal::NotAbove { bound: 20 })
+        );
+        assert_eq!(ledger.check(day!(1), 1, "50"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 1, "not a number"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = ledger(&[(1, "42", SubmitVerdict::Correct)]);
+
+        assert_eq!(
+            ledger.check(day!(1), 1, "43"),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let ledger = ledger(&[(2, "12", SubmitVerdict::TooLow)]);
+        let json = tinyjson::JsonValue::from(ledger.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap().data, ledger.data);
+    }
+
+    #[test]
+    fn parses_submit_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer to decorating the North Pole.",
+                SubmitVerdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high. If you're stuck, ...",
+                SubmitVerdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmitVerdict::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                SubmitVerdict::Incorrect,
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                SubmitVerdict::WrongLevel,
+            ),
+            ("Bad gateway", SubmitVerdict::Unknown("Bad gateway".into())),
+        ];
+
+        for (response, verdict) in cases {
+            assert_eq!(
+                SubmitVerdict::from_response(response),
+                verdict,
+                "{response}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_wait_time() {
+        let verdict = |response: &str| SubmitVerdict::from_response(response);
+
+        assert_eq!(
+            verdict(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 45s left to wait."
+            ),
+            SubmitVerdict::RateLimited {
+                wait: Some(Duration::from_secs(45))
+            }
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently. You have 4m 52s left to wait."),
+            SubmitVerdict::RateLimited {
+                wait: Some(Duration::from_secs(292))
+            }
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently."),
+            SubmitVerdict::RateLimited { wait: None }
+        );
+    }
+}
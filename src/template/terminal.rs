@@ -0,0 +1,342 @@
+//! Renders puzzle descriptions in the terminal.
+//! Understands the markdown written by [`html::to_markdown`](super::html::to_markdown): headings, paragraphs,
+//! lists, fenced code blocks and inline code, emphasis and links.
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_SIZE: (usize, usize) = (80, 24);
+
+/// Descriptions are not wrapped wider than this, even on wide terminals.
+const MAX_WIDTH: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Emphasis,
+    Code,
+    /// Emphasized code, which is how puzzles highlight answers.
+    Highlight,
+}
+
+impl Style {
+    fn ansi(self) -> String {
+        match self {
+            Style::Plain => String::new(),
+            Style::Emphasis => ANSI_BOLD.into(),
+            Style::Code => ANSI_ITALIC.into(),
+            Style::Highlight => format!("{ANSI_BOLD}{ANSI_ITALIC}"),
+        }
+    }
+}
+
+/// A run of text without spaces that is rendered in one style.
+type Segment = (String, Style);
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Item(String),
+    Code(Vec<String>),
+}
+
+/// Renders markdown as text for the terminal, wrapping paragraphs to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let width = width.clamp(20, MAX_WIDTH);
+    let blocks = blocks(markdown);
+    let mut out = String::new();
+
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            // items of a list are not separated by empty lines.
+            let is_list = matches!((&blocks[i - 1], block), (Block::Item(_), Block::Item(_)));
+            out.push_str(if is_list { "\n" } else { "\n\n" });
+        }
+
+        match block {
+            Block::Heading(text) => out.push_str(&format!("{ANSI_BOLD}{text}{ANSI_RESET}")),
+            Block::Paragraph(text) => out.push_str(&wrap(&inline(text), width, "", "")),
+            Block::Item(text) => out.push_str(&wrap(&inline(text), width, "  - ", "    ")),
+            Block::Code(lines) => {
+                let lines: Vec<String> = lines
+                    .iter()
+                    .map(|line| format!("    {ANSI_BOLD}{line}{ANSI_RESET}"))
+                    .collect();
+                out.push_str(&lines.join("\n"));
+            }
+        }
+    }
+
+    out.push('\n');
+    out
+}
+
+fn blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut code: Option<Vec<String>> = None;
+
+    let flush = |blocks: &mut Vec<Block>, paragraph: &mut Vec<&str>| {
+        if paragraph.is_empty() {
+            return;
+        }
+        let text = paragraph.join(" ");
+        paragraph.clear();
+
+        blocks.push(if let Some(heading) = text.strip_prefix("## ") {
+            Block::Heading(heading.trim().to_string())
+        } else if let Some(item) = text.strip_prefix("- ") {
+            Block::Item(item.to_string())
+        } else {
+            Block::Paragraph(text)
+        });
+    };
+
+    for line in markdown.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match (&mut code, is_fence) {
+            (Some(lines), true) => {
+                blocks.push(Block::Code(std::mem::take(lines)));
+                code = None;
+            }
+            (Some(lines), false) => lines.push(line.to_string()),
+            (None, true) => {
+                flush(&mut blocks, &mut paragraph);
+                code = Some(vec![]);
+            }
+            (None, false) if line.trim().is_empty() => flush(&mut blocks, &mut paragraph),
+            (None, false) => {
+                // every list item is a block of its own.
+                if line.starts_with("- ") {
+                    flush(&mut blocks, &mut paragraph);
+                }
+                paragraph.push(line);
+            }
+        }
+    }
+
+    flush(&mut blocks, &mut paragraph);
+
+    // an unterminated code block is rendered as is.
+    if let Some(lines) = code {
+        blocks.push(Block::Code(lines));
+    }
+
+    blocks
+}
+
+/// Splits a paragraph into words, each made of styled segments.
+fn inline(text: &str) -> Vec<Vec<Segment>> {
+    let mut words: Vec<Vec<Segment>> = vec![vec![]];
+    let mut emphasis = false;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if let Some(code) = rest.strip_prefix("*`")
+            && let Some(end) = code.find("`*")
+        {
+            push_words(&mut words, &code[..end], Style::Highlight);
+            rest = &code[end + 2..];
+        } else if let Some(code) = rest.strip_prefix('`')
+            && let Some(end) = code.find('`')
+        {
+            let (content, style) = match code[..end]
+                .strip_prefix('*')
+                .and_then(|c| c.strip_suffix('*'))
+            {
+                Some(content) if !content.is_empty() => (content, Style::Highlight),
+                _ => (&code[..end], Style::Code),
+            };
+            push_words(&mut words, content, style);
+            rest = &code[end + 1..];
+        } else if let Some(link) = rest.strip_prefix('[')
+            && let Some(end) = link.find("](")
+            && let Some(href_end) = link[end..].find(')')
+        {
+            let style = if emphasis {
+                Style::Emphasis
+            } else {
+                Style::Plain
+            };
+            push_words(&mut words, &link[..end], style);
+            rest = &link[end + href_end + 1..];
+        } else if let Some(after) = rest.strip_prefix('*') {
+            emphasis = !emphasis;
+            rest = after;
+        } else {
+            // always consume the first character, it may be a marker without a closing pair.
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let end = rest[first..]
+                .find(['`', '[', '*'])
+                .map_or(rest.len(), |i| i + first);
+            let style = if emphasis {
+                Style::Emphasis
+            } else {
+                Style::Plain
+            };
+            push_words(&mut words, &rest[..end], style);
+            rest = &rest[end..];
+        }
+    }
+
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Appends styled text, starting a new word at every space.
+fn push_words(words: &mut Vec<Vec<Segment>>, text: &str, style: Style) {
+    for (i, part) in text.split(' ').enumerate() {
+        if i > 0 && words.last().is_some_and(|word| !word.is_empty()) {
+            words.push(vec![]);
+        }
+        if !part.is_empty() {
+            words.last_mut().unwrap().push((part.to_string(), style));
+        }
+    }
+}
+
+/// Greedily wraps words to `width` visible columns.
+fn wrap(words: &[Vec<Segment>], width: usize, first_indent: &str, indent: &str) -> String {
+    let mut out = String::from(first_indent);
+    let mut column = first_indent.chars().count();
+    let mut line_start = true;
+
+    for word in words {
+        let len: usize = word.iter().map(|(text, _)| text.chars().count()).sum();
+
+        if !line_start && column + 1 + len > width {
+            out.push('\n');
+            out.push_str(indent);
+            column = indent.chars().count();
+            line_start = true;
+        }
+
+        if !line_start {
+            out.push(' ');
+            column += 1;
+        }
+
+        for (text, style) in word {
+            match style {
+                Style::Plain => out.push_str(text),
+                _ => out.push_str(&format!("{}{text}{ANSI_RESET}", style.ansi())),
+            }
+        }
+
+        column += len;
+        line_start = false;
+    }
+
+    out
+}
+
+/// Size of the terminal as `(columns, rows)`. Falls back to 80x24 if it can not be determined.
+pub fn size() -> (usize, usize) {
+    let from_env = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok());
+
+    if let (Some(columns), Some(rows)) = (from_env("COLUMNS"), from_env("LINES")) {
+        return (columns, rows);
+    }
+
+    // `stty` reads the size from the terminal connected to stdin.
+    Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .and_then(|output| {
+            let size = String::from_utf8(output.stdout).ok()?;
+            let (rows, columns) = size.trim().split_once(' ')?;
+            Some((columns.parse().ok()?, rows.parse().ok()?))
+        })
+        .unwrap_or(DEFAULT_SIZE)
+}
+
+/// Prints text, piping it through `$PAGER` (or `less -R`) if it does not fit on the screen.
+pub fn page(text: &str) {
+    let (_, rows) = size();
+    let stdout = io::stdout();
+
+    if stdout.is_terminal() && text.lines().count() >= rows {
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+        let mut parts = pager.split_whitespace();
+
+        if let Some(program) = parts.next()
+            && let Ok(mut child) = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .spawn()
+        {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the pager might be closed before reading everything.
+                stdin.write_all(text.as_bytes()).ok();
+            }
+            if child.wait().is_ok() {
+                return;
+            }
+        }
+    }
+
+    print!("{text}");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn wraps_paragraphs() {
+        let markdown = "one two three four five six seven eight nine ten eleven twelve";
+        assert_eq!(
+            render(markdown, 24),
+            "one two three four five\nsix seven eight nine ten\neleven twelve\n"
+        );
+    }
+
+    #[test]
+    fn styles_inline_text() {
+        let markdown = "Find the *sum* of `1 2`: *`3`*, see [rules](/about).";
+        assert_eq!(
+            render(markdown, 80),
+            format!(
+                "Find the {ANSI_BOLD}sum{ANSI_RESET} of {ANSI_ITALIC}1{ANSI_RESET} {ANSI_ITALIC}2{ANSI_RESET}: \
+                {ANSI_BOLD}{ANSI_ITALIC}3{ANSI_RESET}, see rules.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn styles_non_ascii_text() {
+        let markdown = "Use `x`—then go.\n\né is a letter, as is *ü*.";
+        assert_eq!(
+            render(markdown, 80),
+            format!(
+                "Use {ANSI_ITALIC}x{ANSI_RESET}—then go.\n\n\
+                é is a letter, as is {ANSI_BOLD}ü{ANSI_RESET}.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let markdown = "## --- Day 1: Test ---\n\nFor example:\n\n```\n1 2 3 4 5 6 7 8 9 10 11 12 13 14 15\n```\n\n- first item\n- second item\n\nDone.";
+        assert_eq!(
+            render(markdown, 20),
+            format!(
+                "{ANSI_BOLD}--- Day 1: Test ---{ANSI_RESET}\n\n\
+                For example:\n\n    \
+                {ANSI_BOLD}1 2 3 4 5 6 7 8 9 10 11 12 13 14 15{ANSI_RESET}\n\n  \
+                - first item\n  \
+                - second item\n\n\
+                Done.\n"
+            )
+        );
+    }
+}
@@ -1,9 +1,12 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{
+    Day, Year,
+    alloc::{self, AllocStats},
+    history::{self, HistoryEntry},
+    stats::{self, Stats},
+};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,33 +14,106 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Time spent in the shared `parse` function, if the day has one.
+    pub parse: Option<String>,
+    pub parse_stats: Option<Stats>,
+    /// Heap usage of the parts and the parse step, only present with the `count-allocs` feature.
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+    pub parse_alloc: Option<AllocStats>,
+    /// Sum of the parse time and both parts.
     pub total_nanos: f64,
 }
 
+/// Timing of day 1 without any results. Meant to fill the remaining fields with `..Default::default()`.
+impl Default for Timing {
+    fn default() -> Self {
+        Self {
+            day: crate::day!(1),
+            part_1: None,
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+            parse_alloc: None,
+            total_nanos: 0_f64,
+        }
+    }
+}
+
+impl Timing {
+    /// Nanoseconds of a part or the parse step, taken from its stats or, for older timings, parsed from its formatted duration.
+    fn part_nanos(formatted: Option<&String>, stats: Option<&Stats>) -> Option<f64> {
+        stats
+            .map(|s| s.mean)
+            .or_else(|| formatted.and_then(|s| history::parse_duration_str(s)))
+    }
+
+    pub fn to_history_entry(&self, timestamp: Option<u64>, commit: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            day: self.day,
+            timestamp,
+            commit: commit.map(ToString::to_string),
+            part_1_nanos: Self::part_nanos(self.part_1.as_ref(), self.part_1_stats.as_ref()),
+            part_2_nanos: Self::part_nanos(self.part_2.as_ref(), self.part_2_stats.as_ref()),
+            parse_nanos: Self::part_nanos(self.parse.as_ref(), self.parse_stats.as_ref()),
+        }
+    }
+}
+
+/// Represents the change of a day's total runtime compared to a stored timing.
+#[derive(Clone, Debug)]
+pub struct TimingDelta {
+    pub day: Day,
+    pub stored_nanos: Option<f64>,
+    pub new_nanos: f64,
+}
+
+impl TimingDelta {
+    /// Relative change in percent, positive values mean that the day got slower.
+    /// Returns `None` if there is no stored timing to compare against.
+    pub fn percent(&self) -> Option<f64> {
+        let stored = self.stored_nanos.filter(|x| *x > 0_f64)?;
+        Some((self.new_nanos - stored) / stored * 100_f64)
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.percent().is_some_and(|p| p > threshold_percent)
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// Append-only log of every stored run.
+    pub history: Vec<HistoryEntry>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(file_path())?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(file_path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The history of `new` is appended to the history of `self`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -51,8 +127,52 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+
+        let history = self
+            .history
+            .iter()
+            .chain(new.history.iter())
+            .cloned()
+            .collect();
+
+        Timings { data, history }
+    }
+
+    /// Returns a copy of these timings with a history entry for every day, stamped with the passed run metadata.
+    pub fn with_history(&self, timestamp: Option<u64>, commit: Option<&str>) -> Self {
+        let history = self
+            .data
+            .iter()
+            .map(|t| t.to_history_entry(timestamp, commit))
+            .collect();
+
+        Timings {
+            data: self.data.clone(),
+            history,
+        }
+    }
+
+    /// All history entries of a day, ordered from oldest to newest.
+    pub fn history_for(&self, day: Day) -> Vec<&HistoryEntry> {
+        self.history.iter().filter(|h| h.day == day).collect()
+    }
+
+    /// Compare `new` timings against `self`, returning one delta per day of `new` that has timings.
+    pub fn compare(&self, new: &Self) -> Vec<TimingDelta> {
+        new.data
+            .iter()
+            .filter(|t| t.part_1.is_some() || t.part_2.is_some() || t.parse.is_some())
+            .map(|t| TimingDelta {
+                day: t.day,
+                stored_nanos: self
+                    .data
+                    .iter()
+                    .find(|s| s.day == t.day)
+                    .map(|s| s.total_nanos),
+                new_nanos: t.total_nanos,
+            })
+            .collect()
     }
 
     /// Sum up total duration of timings as millis.
@@ -67,6 +187,11 @@ impl Timings {
     }
 }
 
+/// Path of the timings file of the selected year, e.g. `data/2025/timings.json`.
+fn file_path() -> PathBuf {
+    Year::selected().data_dir().join("timings.json")
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -78,6 +203,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,20 +218,37 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json_map = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json_map
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
-        Ok(Timings {
-            data: json_data
+        let data: Vec<Timing> = json_data
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let history = match json_map.get("history") {
+            Some(json_history) => json_history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
                 .iter()
-                .map(Timing::try_from)
+                .map(HistoryEntry::try_from)
                 .collect::<Result<_, _>>()?,
-        })
+            // NOTE: files written before the history was introduced only hold a snapshot.
+            // migrate it into the first history entry of each day.
+            None => data
+                .iter()
+                .map(|t| t.to_history_entry(None, None))
+                .collect(),
+        };
+
+        Ok(Timings { data, history })
     }
 }
 
@@ -116,6 +263,7 @@ impl From<&Timing> for JsonValue {
 
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
+        let parse = value.parse.clone().map(JsonValue::String);
 
         map.insert(
             "part_1".into(),
@@ -133,6 +281,44 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            stats::optional_to_json(value.part_1_stats.as_ref()),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            stats::optional_to_json(value.part_2_stats.as_ref()),
+        );
+
+        map.insert(
+            "parse_stats".into(),
+            stats::optional_to_json(value.parse_stats.as_ref()),
+        );
+
+        map.insert(
+            "part_1_alloc".into(),
+            alloc::optional_to_json(value.part_1_alloc.as_ref()),
+        );
+
+        map.insert(
+            "part_2_alloc".into(),
+            alloc::optional_to_json(value.part_2_alloc.as_ref()),
+        );
+
+        map.insert(
+            "parse_alloc".into(),
+            alloc::optional_to_json(value.parse_alloc.as_ref()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -161,15 +347,40 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // NOTE: parse timings are optional to support days and timings without a parse step.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats are optional to support timings stored before they were introduced.
+        let part_1_stats = stats::optional_from_json(json, "part_1_stats")?;
+        let part_2_stats = stats::optional_from_json(json, "part_2_stats")?;
+        let parse_stats = stats::optional_from_json(json, "parse_stats")?;
+        let part_1_alloc = alloc::optional_from_json(json, "part_1_alloc")?;
+        let part_2_alloc = alloc::optional_from_json(json, "part_2_alloc")?;
+        let parse_alloc = alloc::optional_from_json(json, "parse_alloc")?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            parse: parse.cloned(),
+            parse_stats,
+            part_1_alloc,
+            part_2_alloc,
+            parse_alloc,
             total_nanos,
         })
     }
@@ -191,20 +402,23 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         }
     }
 
@@ -223,6 +437,43 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean": 1000000, "min": 900000, "max": 1500000, "median": 950000, "p95": 1400000, "std_dev": 100000, "outliers": 2, "samples": 1000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median, 950_000_f64);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(stats.samples, 1000);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn migrates_snapshot_without_history() {
+            let json = r#"{ "data": [{ "day": "08", "part_1": "1.8s", "part_2": "120.0ms", "total_nanos": 1920000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let history = timings.history_for(day!(8));
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].timestamp, None);
+            assert_eq!(history[0].commit, None);
+            assert_eq!(history[0].part_1_nanos, Some(1_800_000_000_f64));
+            assert_eq!(history[0].part_2_nanos, Some(120_000_000_f64));
+        }
+
+        #[test]
+        fn handles_json_history() {
+            let json = r#"{ "data": [], "history": [{ "day": "02", "timestamp": 1733011200, "commit": "572a7af", "part_1_nanos": 81400000, "part_2_nanos": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 1);
+            let entry = &timings.history[0];
+            assert_eq!(entry.day, day!(2));
+            assert_eq!(entry.timestamp, Some(1_733_011_200));
+            assert_eq!(entry.commit.as_deref(), Some("572a7af"));
+            assert_eq!(entry.part_1_nanos, Some(81_400_000_f64));
+            assert_eq!(entry.part_2_nanos, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -282,10 +533,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -294,12 +547,13 @@ mod tests {
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +561,70 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    ..Default::default()
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        fn timing(day: crate::template::Day, total_nanos: f64) -> Timing {
+            Timing {
+                day,
+                part_1: Some("1ms".into()),
+                total_nanos,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn computes_percentage_deltas() {
+            let stored = get_mock_timings();
+            let new = Timings {
+                data: vec![timing(day!(1), 3.3e+10), timing(day!(2), 3.5e+10)],
+                ..Default::default()
+            };
+            let deltas = stored.compare(&new);
+            assert_eq!(deltas.len(), 2);
+            assert!((deltas[0].percent().unwrap() - 10_f64).abs() < 1e-9);
+            assert!((deltas[1].percent().unwrap() + 50_f64).abs() < 1e-9);
+            assert_eq!(deltas[0].is_regression(5_f64), true);
+            assert_eq!(deltas[0].is_regression(15_f64), false);
+            assert_eq!(deltas[1].is_regression(0_f64), false);
+        }
+
+        #[test]
+        fn handles_days_without_stored_timings() {
+            let stored = get_mock_timings();
+            let new = Timings {
+                data: vec![timing(day!(3), 1e+6)],
+                ..Default::default()
+            };
+            let deltas = stored.compare(&new);
+            assert_eq!(deltas[0].percent(), None);
+            assert_eq!(deltas[0].is_regression(0_f64), false);
+        }
+
+        #[test]
+        fn skips_unsolved_days() {
+            let stored = get_mock_timings();
+            let mut unsolved = timing(day!(1), 0_f64);
+            unsolved.part_1 = None;
+            let new = Timings {
+                data: vec![unsolved],
+                ..Default::default()
+            };
+            assert_eq!(stored.compare(&new).len(), 0);
         }
     }
 
@@ -331,10 +642,9 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -351,10 +661,9 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +674,21 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn appends_history() {
+            let timings = get_mock_timings().with_history(Some(1), Some("a"));
+            let other = get_mock_timings().with_history(Some(2), Some("b"));
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.history.len(), 6);
+
+            let day_1 = merged.history_for(day!(1));
+            assert_eq!(day_1.len(), 2);
+            assert_eq!(day_1[0].timestamp, Some(1));
+            assert_eq!(day_1[1].commit.as_deref(), Some("b"));
+            assert_eq!(day_1[1].part_1_nanos, Some(10_000_000_f64));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
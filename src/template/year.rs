@@ -0,0 +1,188 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with a shortened calendar of 12 days.
+const SHORT_CALENDAR_SINCE: u16 = 2025;
+
+/// Year used if `AOC_YEAR` is not set, neither at runtime nor when compiling.
+const DEFAULT_YEAR: u16 = 2025;
+
+/// An advent of code event, i.e. a year from 2015 onwards.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.days(), 25);
+/// assert_eq!(year.to_string(), "2024");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] if there was an advent of code event in it, returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        (year >= FIRST_YEAR).then_some(Self(year))
+    }
+
+    /// The year selected with the `AOC_YEAR` environment variable.
+    /// Falls back to the value of `AOC_YEAR` at compile time, then to the latest supported year.
+    pub fn selected() -> Self {
+        env::var("AOC_YEAR")
+            .ok()
+            .and_then(|year| year.parse().ok())
+            .unwrap_or_else(Self::__compile_time)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Number of days in the calendar of this year.
+    pub fn days(self) -> u8 {
+        if self.0 >= SHORT_CALENDAR_SINCE {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Directory holding the inputs, examples, puzzles and stored data of this year.
+    pub fn data_dir(self) -> PathBuf {
+        PathBuf::from("data").join(self.to_string())
+    }
+
+    /// Name of the solution binary of a day in this year, e.g. `2024-01`.
+    pub fn bin_name(self, day: Day) -> String {
+        format!("{self}-{day}")
+    }
+
+    /// Path of the solution binary of a day in this year, e.g. `./src/bin/2024-01.rs`.
+    pub fn bin_path(self, day: Day) -> String {
+        format!("./src/bin/{}.rs", self.bin_name(day))
+    }
+
+    /// Year of a solution binary named like `2024-01`, or the year of the template if the name has no year.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_bin_name(name: &str) -> Self {
+        let bytes = name.as_bytes();
+
+        if bytes.len() > 4
+            && bytes[4] == b'-'
+            && let Some(year) = parse_year(bytes.split_at(4).0)
+        {
+            return Self(year);
+        }
+
+        Self::__compile_time()
+    }
+
+    /// The value of `AOC_YEAR` at compile time, usable in a const context.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __compile_time() -> Self {
+        match option_env!("AOC_YEAR") {
+            Some(value) => match parse_year(value.as_bytes()) {
+                Some(year) => Self(year),
+                None => Self(DEFAULT_YEAR),
+            },
+            None => Self(DEFAULT_YEAR),
+        }
+    }
+}
+
+/// Parses four ascii digits into a valid year.
+const fn parse_year(bytes: &[u8]) -> Option<u16> {
+    if bytes.len() != 4 {
+        return None;
+    }
+
+    let mut year = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    if year < FIRST_YEAR { None } else { Some(year) }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.trim().parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year from {FIRST_YEAR} onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn calendar_length() {
+        assert_eq!(Year::new(2015).unwrap().days(), 25);
+        assert_eq!(Year::new(2024).unwrap().days(), 25);
+        assert_eq!(Year::new(2025).unwrap().days(), 12);
+        assert_eq!(Year::new(2014), None);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2019".parse::<Year>().ok(), Year::new(2019));
+        assert!("1999".parse::<Year>().is_err());
+        assert!("twenty".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn year_of_bin_name() {
+        assert_eq!(Year::__from_bin_name("2017-03"), Year(2017));
+        assert_eq!(
+            Year::__from_bin_name("advent_of_code"),
+            Year::__compile_time()
+        );
+        assert_eq!(Year::__from_bin_name("0001-03"), Year::__compile_time());
+    }
+
+    #[test]
+    fn bin_names() {
+        let year = Year(2016);
+        assert_eq!(year.bin_name(crate::day!(4)), "2016-04");
+        assert_eq!(year.bin_path(crate::day!(4)), "./src/bin/2016-04.rs");
+    }
+}